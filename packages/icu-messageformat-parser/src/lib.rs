@@ -2,6 +2,8 @@ mod ast;
 mod parser;
 mod pattern_syntax;
 mod intl;
+mod printer;
 
-pub use ast::{Ast, AstElement, Error, Position, Span};
+pub use ast::{Ast, AstElement, Error, ErrorKind, PluralType, Position, Span};
 pub use parser::{Parser, ParserOptions};
+pub use printer::print_ast;
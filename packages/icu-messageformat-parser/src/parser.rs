@@ -1793,7 +1793,7 @@ fn parse_number_skeleton_from_string(
     })
 }
 
-fn is_potential_element_name_char(ch: char) -> bool {
+pub(crate) fn is_potential_element_name_char(ch: char) -> bool {
     matches!(ch, '-'
         | '.'
         | '0'..='9'
@@ -0,0 +1,246 @@
+use crate::{
+    ast::{Ast, AstElement, DateTimeArgStyle, NumberArgStyle, PluralOrSelectOptions, PluralType},
+    parser::is_potential_element_name_char,
+};
+
+/// Print the AST back to an ICU message, which parses to the same AST apart from
+/// locations. Adapted from:
+/// https://github.com/formatjs/formatjs/blob/main/packages/icu-messageformat-parser/printer.ts
+///
+/// Unlike formatjs' printer, literal text is quoted only where needed, so text like
+/// `Don't` is printed as is.
+pub fn print_ast(ast: &Ast) -> String {
+    print_elements(ast, false, None)
+}
+
+/// Print the elements of a message, followed by `following` (the closing `}` of a branch
+/// or the closing tag), which decides whether a trailing apostrophe has to be doubled.
+fn print_elements(ast: &[AstElement], in_plural: bool, following: Option<char>) -> String {
+    // Printed backwards, since literals depend on the first character of what follows.
+    let mut printed: Vec<String> = Vec::with_capacity(ast.len());
+    for element in ast.iter().rev() {
+        let next = printed
+            .last()
+            .and_then(|next| next.chars().next())
+            .or(following);
+        printed.push(print_element(element, in_plural, next));
+    }
+    printed.reverse();
+    printed.concat()
+}
+
+fn print_element(element: &AstElement, in_plural: bool, following: Option<char>) -> String {
+    match element {
+        AstElement::Literal { value, .. } => print_literal(value, in_plural, following),
+        AstElement::Argument { value, .. } => format!("{{{}}}", value),
+        AstElement::Number { value, style, .. } => match style {
+            None => format!("{{{}, number}}", value),
+            Some(NumberArgStyle::Style(style)) => format!("{{{}, number, {}}}", value, style),
+            Some(NumberArgStyle::Skeleton(skeleton)) => {
+                let tokens: Vec<String> = skeleton
+                    .tokens
+                    .iter()
+                    .map(|token| {
+                        let mut printed = token.stem.to_string();
+                        for option in &token.options {
+                            printed.push('/');
+                            printed.push_str(option);
+                        }
+                        printed
+                    })
+                    .collect();
+                format!("{{{}, number, ::{}}}", value, tokens.join(" "))
+            }
+        },
+        AstElement::Date { value, style, .. } => print_date_time(value, "date", style),
+        AstElement::Time { value, style, .. } => print_date_time(value, "time", style),
+        AstElement::Select { value, options, .. } => {
+            format!("{{{}, select, {}}}", value, print_options(options, false))
+        }
+        AstElement::Plural {
+            value,
+            plural_type,
+            offset,
+            options,
+            ..
+        } => {
+            let plural_type = match plural_type {
+                PluralType::Cardinal => "plural",
+                PluralType::Ordinal => "selectordinal",
+            };
+            let offset = if *offset != 0 {
+                format!("offset:{} ", offset)
+            } else {
+                String::new()
+            };
+            format!(
+                "{{{}, {}, {}{}}}",
+                value,
+                plural_type,
+                offset,
+                print_options(options, true)
+            )
+        }
+        AstElement::Pound(..) => "#".to_string(),
+        AstElement::Tag {
+            value, children, ..
+        } => format!(
+            "<{}>{}</{}>",
+            value,
+            print_elements(children, in_plural, Some('<')),
+            value
+        ),
+    }
+}
+
+fn print_date_time(value: &str, arg_type: &str, style: &Option<DateTimeArgStyle>) -> String {
+    match style {
+        None => format!("{{{}, {}}}", value, arg_type),
+        Some(DateTimeArgStyle::Style(style)) => format!("{{{}, {}, {}}}", value, arg_type, style),
+        Some(DateTimeArgStyle::Skeleton(skeleton)) => {
+            format!("{{{}, {}, ::{}}}", value, arg_type, skeleton.pattern)
+        }
+    }
+}
+
+fn print_options(options: &PluralOrSelectOptions, in_plural: bool) -> String {
+    options
+        .0
+        .iter()
+        .map(|(selector, option)| {
+            format!(
+                "{} {{{}}}",
+                selector,
+                print_elements(&option.value, in_plural, Some('}'))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether the literal is a self-closing tag, which is parsed as a literal of its own.
+fn is_self_closing_tag(value: &str) -> bool {
+    value
+        .strip_prefix('<')
+        .and_then(|value| value.strip_suffix("/>"))
+        .is_some_and(|name| {
+            name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && name.chars().all(is_potential_element_name_char)
+        })
+}
+
+/// Print literal text, quoting the characters which would be parsed as syntax otherwise
+/// and doubling apostrophes which would start quoted text.
+fn print_literal(value: &str, in_plural: bool, following: Option<char>) -> String {
+    if is_self_closing_tag(value) {
+        return value.to_string();
+    }
+
+    let chars: Vec<char> = value.chars().collect();
+    let is_syntax = |idx: usize| match chars[idx] {
+        '{' | '}' => true,
+        '#' => in_plural,
+        '<' => chars
+            .get(idx + 1)
+            .is_some_and(|c| c.is_ascii_alphabetic() || *c == '/'),
+        _ => false,
+    };
+    // An apostrophe starts quoted text before these characters.
+    let starts_quote = |c: Option<char>| match c {
+        Some('{' | '}' | '<' | '>' | '\'') => true,
+        Some('#') => in_plural,
+        _ => false,
+    };
+
+    let mut printed = String::with_capacity(value.len());
+    let mut idx = 0;
+    while idx < chars.len() {
+        if is_syntax(idx) {
+            printed.push('\'');
+            while idx < chars.len() && (is_syntax(idx) || chars[idx] == '\'') {
+                if chars[idx] == '\'' {
+                    printed.push_str("''");
+                } else {
+                    printed.push(chars[idx]);
+                }
+                idx += 1;
+            }
+            printed.push('\'');
+            continue;
+        }
+
+        if chars[idx] == '\'' && starts_quote(chars.get(idx + 1).copied().or(following)) {
+            printed.push_str("''");
+        } else {
+            printed.push(chars[idx]);
+        }
+        idx += 1;
+    }
+    printed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, ParserOptions};
+
+    fn reprint(message: &str) -> String {
+        let options = ParserOptions::default();
+        let mut parser = Parser::new(message, &options);
+        print_ast(&parser.parse().unwrap())
+    }
+
+    #[test]
+    fn print_arguments() {
+        for message in [
+            "Hello {name}, you have {count, number} items",
+            "{count, number, ::currency/USD compact-short} {n, number, percent}",
+            "{d, date, short} {t, time, ::hhmm}",
+            "{gender, select, male {He} female {She} other {They}}",
+            "{count, plural, offset:1 =0 {no items} one {# item} other {# items}}",
+            "{place, selectordinal, one {#st} other {#th}}",
+            "Click <a>here</a> or <b>there <i>{name}</i></b>",
+        ] {
+            similar_asserts::assert_eq!(reprint(message), message);
+        }
+    }
+
+    #[test]
+    fn print_canonical_whitespace() {
+        similar_asserts::assert_eq!(
+            reprint("{ count ,plural,one{# item}\n other {# items} }"),
+            "{count, plural, one {# item} other {# items}}"
+        );
+    }
+
+    #[test]
+    fn print_quoted_literals() {
+        for (message, printed) in [
+            ("Don't", "Don't"),
+            ("It's '{'name'}'", "It's '{'name'}'"),
+            ("'{name}'", "'{'name'}'"),
+            ("'{  }'", "'{'  '}'"),
+            ("users' {count}", "users' {count}"),
+            ("users''{count}", "users''{count}"),
+            ("It'''{'s'}'", "It'''{'s'}'"),
+            ("''", "'"),
+            ("a < b, <3, '<b>'", "a < b, <3, '<'b>"),
+            ("line<br/>break", "line<br/>break"),
+            (
+                "{count, plural, other {'#' is #}}",
+                "{count, plural, other {'#' is #}}",
+            ),
+            (
+                "{count, plural, other {it's}}",
+                "{count, plural, other {it's}}",
+            ),
+            (
+                "{count, plural, other {users''}}",
+                "{count, plural, other {users''}}",
+            ),
+        ] {
+            similar_asserts::assert_eq!(reprint(message), printed, "{}", message);
+            similar_asserts::assert_eq!(reprint(printed), printed, "{}", printed);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use swc_core::ecma::ast::{Expr, Lit, ObjectPatProp, Pat, PropName};
+
+/// Tracks which local bindings refer to `formatMessage` (or one of the additional function
+/// names) in the lexical scopes the visitor is currently in, i.e.
+/// `const {formatMessage: t} = useIntl()` or `const fm = intl.formatMessage`.
+///
+/// Bindings are recorded as their declarations are visited, and a binding of the same name
+/// in an inner scope which is not an alias shadows the outer one.
+#[derive(Debug, Clone, Default)]
+pub struct FormatMessageScopes {
+    scopes: Vec<HashMap<String, bool>>,
+}
+
+impl FormatMessageScopes {
+    pub fn push(&mut self) {
+        self.scopes.push(Default::default());
+    }
+
+    pub fn pop(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub fn declare(&mut self, name: String, is_alias: bool) {
+        if self.scopes.is_empty() {
+            self.push();
+        }
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, is_alias);
+        }
+    }
+
+    /// Whether the name is declared in a scope nested in the module scope, shadowing
+    /// bindings of the module and globals.
+    pub fn is_shadowed(&self, name: &str) -> bool {
+        self.scopes
+            .iter()
+            .skip(1)
+            .any(|scope| scope.contains_key(name))
+    }
+
+    /// Returns whether the innermost binding of the name is an alias, or `None` if
+    /// there is no binding of the name in the tracked scopes.
+    pub fn lookup(&self, name: &str) -> Option<bool> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+    }
+}
+
+/// Returns the name of a static property key, `foo`, `'foo'` and `['foo']` alike.
+pub fn get_static_prop_name(name: &PropName) -> Option<&str> {
+    match name {
+        PropName::Ident(ident) => Some(&ident.sym),
+        PropName::Str(str) => Some(&str.value),
+        PropName::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(str)) => Some(&str.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Collects all bindings declared by a pattern, along with whether the binding is
+/// destructured from a property named as one of the function names, like `t` in
+/// `{formatMessage: t}` or `formatMessage` in `{intl: {formatMessage}}`.
+pub fn collect_pat_bindings(
+    pat: &Pat,
+    is_function_name: &dyn Fn(&str) -> bool,
+    bindings: &mut Vec<(String, bool)>,
+) {
+    match pat {
+        Pat::Ident(ident) => bindings.push((ident.id.sym.to_string(), false)),
+        Pat::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                collect_pat_bindings(elem, is_function_name, bindings);
+            }
+        }
+        Pat::Rest(rest) => collect_pat_bindings(&rest.arg, is_function_name, bindings),
+        Pat::Assign(assign) => collect_pat_bindings(&assign.left, is_function_name, bindings),
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => {
+                        let is_alias = get_static_prop_name(&kv.key).is_some_and(is_function_name);
+                        // `{formatMessage: t = fallback}`
+                        let value = match &*kv.value {
+                            Pat::Assign(assign) => &*assign.left,
+                            value => value,
+                        };
+                        match value {
+                            Pat::Ident(ident) => {
+                                bindings.push((ident.id.sym.to_string(), is_alias))
+                            }
+                            value => collect_pat_bindings(value, is_function_name, bindings),
+                        }
+                    }
+                    ObjectPatProp::Assign(assign) => bindings.push((
+                        assign.key.sym.to_string(),
+                        is_function_name(&assign.key.sym),
+                    )),
+                    ObjectPatProp::Rest(rest) => {
+                        collect_pat_bindings(&rest.arg, is_function_name, bindings)
+                    }
+                }
+            }
+        }
+        Pat::Invalid(..) | Pat::Expr(..) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::{
+        common::{FileName, SourceMap},
+        ecma::{
+            ast::{Decl, Stmt},
+            parser::parse_file_as_script,
+        },
+    };
+
+    use super::*;
+
+    fn collect(src: &str) -> Vec<(String, bool)> {
+        let source_map = SourceMap::default();
+        let file = source_map.new_source_file(FileName::Anon, src.into());
+        let script = parse_file_as_script(
+            &file,
+            Default::default(),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .expect("Should parse");
+        let pat = match &script.body[0] {
+            Stmt::Decl(Decl::Var(var_decl)) => &var_decl.decls[0].name,
+            _ => panic!("Should be a variable declaration"),
+        };
+
+        let mut bindings = vec![];
+        collect_pat_bindings(pat, &|name| name == "formatMessage", &mut bindings);
+        bindings
+    }
+
+    fn binding(name: &str, is_alias: bool) -> (String, bool) {
+        (name.to_string(), is_alias)
+    }
+
+    #[test]
+    fn destructured_bindings() {
+        assert_eq!(
+            collect("const {formatMessage: t, 'formatMessage': u, ['formatMessage']: v} = intl"),
+            vec![binding("t", true), binding("u", true), binding("v", true)]
+        );
+        assert_eq!(
+            collect("const {intl: {formatMessage}, formatMessage: t = fallback, ...rest} = props"),
+            vec![
+                binding("formatMessage", true),
+                binding("t", true),
+                binding("rest", false)
+            ]
+        );
+        assert_eq!(
+            collect("const [formatMessage, {formatDate: t}] = fns"),
+            vec![binding("formatMessage", false), binding("t", false)]
+        );
+    }
+
+    #[test]
+    fn shadowing() {
+        let mut scopes = FormatMessageScopes::default();
+        scopes.declare("t".into(), true);
+        assert_eq!(scopes.lookup("t"), Some(true));
+
+        scopes.push();
+        scopes.declare("t".into(), false);
+        assert_eq!(scopes.lookup("t"), Some(false));
+        assert_eq!(scopes.lookup("formatMessage"), None);
+        assert!(scopes.is_shadowed("t"));
+
+        scopes.pop();
+        assert_eq!(scopes.lookup("t"), Some(true));
+        assert!(!scopes.is_shadowed("t"));
+    }
+}
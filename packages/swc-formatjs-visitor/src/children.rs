@@ -0,0 +1,253 @@
+use swc_core::{
+    common::{EqIgnoreSpan, Span, Spanned, DUMMY_SP},
+    ecma::ast::{
+        ArrowExpr, BlockStmtOrExpr, Expr, Ident, JSXClosingElement, JSXElement, JSXElementChild,
+        JSXElementName, JSXExpr, JSXExprContainer, JSXMemberExpr, JSXNamespacedName,
+        JSXOpeningElement, KeyValueProp, MemberExpr, MemberProp, ObjectLit, Prop, PropName,
+        PropOrSpread, Str,
+    },
+};
+
+use crate::{entities::get_jsx_text_value, evaluate::ConstBindings, literal::is_identifier_name};
+
+/// Name of the argument of rich text tag render functions.
+static CHUNKS: &str = "chunks";
+
+/// The ICU message of the JSX children of a message component, i.e.
+/// `Hello <b>{name}</b>` for `<T>Hello <b>{name}</b></T>`.
+#[derive(Debug, Clone, Default)]
+pub struct ChildrenMessage {
+    pub message: String,
+    /// Values of the arguments and tags of the message, in order of appearance.
+    pub values: Vec<(String, Expr)>,
+}
+
+impl ChildrenMessage {
+    /// Serialize the children to a message. Text and statically evaluate-able expressions
+    /// become literal text, other expressions become `{arg}` arguments and elements
+    /// become `<tag>` rich text tags (or `{arg}` arguments if they have no children).
+    /// Returns the span of the first child which cannot be serialized otherwise.
+    pub fn serialize(children: &[JSXElementChild], bindings: &ConstBindings) -> Result<Self, Span> {
+        let mut message = ChildrenMessage::default();
+        message.push_children(children, bindings)?;
+        Ok(message)
+    }
+
+    /// Object literal of the values, passed as `values` of `FormattedMessage`.
+    pub fn values_object(&self) -> Expr {
+        Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: self
+                .values
+                .iter()
+                .map(|(name, value)| {
+                    let prop = match value {
+                        Expr::Ident(ident) if &*ident.sym == name => Prop::Shorthand(ident.clone()),
+                        _ => Prop::KeyValue(KeyValueProp {
+                            key: if is_identifier_name(name) {
+                                PropName::Ident(Ident::new(name.as_str().into(), DUMMY_SP))
+                            } else {
+                                PropName::Str(Str::from(name.as_str()))
+                            },
+                            value: Box::new(value.clone()),
+                        }),
+                    };
+                    PropOrSpread::Prop(Box::new(prop))
+                })
+                .collect(),
+        })
+    }
+
+    fn push_children(
+        &mut self,
+        children: &[JSXElementChild],
+        bindings: &ConstBindings,
+    ) -> Result<(), Span> {
+        for child in children {
+            match child {
+                JSXElementChild::JSXText(text) => {
+                    self.push_text(&clean_jsx_text(&get_jsx_text_value(text)));
+                }
+                JSXElementChild::JSXExprContainer(JSXExprContainer { expr, .. }) => match expr {
+                    JSXExpr::JSXEmptyExpr(..) => {}
+                    JSXExpr::Expr(expr) => {
+                        if let Some(text) = bindings.evaluate_string(expr) {
+                            self.push_text(&text);
+                        } else {
+                            let name = self.add_value(get_arg_name(expr), (**expr).clone());
+                            self.message.push_str(&format!("{{{}}}", name));
+                        }
+                    }
+                },
+                JSXElementChild::JSXFragment(fragment) => {
+                    self.push_children(&fragment.children, bindings)?;
+                }
+                JSXElementChild::JSXElement(element) => {
+                    let tag = get_tag_name(&element.opening.name);
+                    if element.children.is_empty() {
+                        let name = self.add_value(tag, Expr::JSXElement(element.clone()));
+                        self.message.push_str(&format!("{{{}}}", name));
+                    } else {
+                        let name = self.add_value(tag, create_tag_fn(&element.opening));
+                        self.message.push_str(&format!("<{}>", name));
+                        self.push_children(&element.children, bindings)?;
+                        self.message.push_str(&format!("</{}>", name));
+                    }
+                }
+                JSXElementChild::JSXSpreadChild(spread) => return Err(spread.span()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' | '}' | '<' | '>' => {
+                    self.message.push('\'');
+                    self.message.push(c);
+                    self.message.push('\'');
+                }
+                // An apostrophe only starts quoted text before a special character, or
+                // before the arguments and tags following the text.
+                '\'' if matches!(chars.peek(), None | Some('\'' | '{' | '}' | '<' | '>')) => {
+                    self.message.push_str("''");
+                }
+                _ => self.message.push(c),
+            }
+        }
+    }
+
+    /// Add a value, reusing the name of an identical value. Returns the name of the value.
+    fn add_value(&mut self, base: &str, value: Expr) -> String {
+        let mut name = base.to_string();
+        let mut index = 2;
+        loop {
+            match self.values.iter().find(|(existing, _)| *existing == name) {
+                None => {
+                    self.values.push((name.clone(), value));
+                    return name;
+                }
+                Some((_, existing)) if existing.eq_ignore_span(&value) => return name,
+                Some(_) => {
+                    name = format!("{}{}", base, index);
+                    index += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Whitespace of JSX text the way React renders it: lines are trimmed, and lines
+/// containing only whitespace are removed.
+pub fn clean_jsx_text(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let last_non_empty_line = lines.iter().rposition(|line| !line.trim().is_empty());
+
+    let mut cleaned = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let mut line = *line;
+        if index != 0 {
+            line = line.trim_start();
+        }
+        if index != lines.len() - 1 || text.ends_with('\n') {
+            line = line.trim_end();
+        }
+        if !line.is_empty() {
+            cleaned.push_str(line);
+            if Some(index) != last_non_empty_line {
+                cleaned.push(' ');
+            }
+        }
+    }
+    cleaned
+}
+
+fn get_arg_name(expr: &Expr) -> &str {
+    match expr {
+        Expr::Ident(ident) => &ident.sym,
+        Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => &prop.sym,
+        Expr::Paren(paren) => get_arg_name(&paren.expr),
+        _ => "value",
+    }
+}
+
+fn get_tag_name(name: &JSXElementName) -> &str {
+    match name {
+        JSXElementName::Ident(ident) => &ident.sym,
+        JSXElementName::JSXMemberExpr(JSXMemberExpr { prop, .. }) => &prop.sym,
+        JSXElementName::JSXNamespacedName(JSXNamespacedName { name, .. }) => &name.sym,
+    }
+}
+
+/// `(chunks) => <tag {...attrs}>{chunks}</tag>`
+fn create_tag_fn(opening: &JSXOpeningElement) -> Expr {
+    let chunks = Ident::new(CHUNKS.into(), DUMMY_SP);
+
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![chunks.clone().into()],
+        body: BlockStmtOrExpr::Expr(Box::new(Expr::JSXElement(Box::new(JSXElement {
+            span: DUMMY_SP,
+            opening: JSXOpeningElement {
+                span: DUMMY_SP,
+                self_closing: false,
+                ..opening.clone()
+            },
+            children: vec![JSXElementChild::JSXExprContainer(JSXExprContainer {
+                span: DUMMY_SP,
+                expr: JSXExpr::Expr(Box::new(Expr::Ident(chunks))),
+            })],
+            closing: Some(JSXClosingElement {
+                span: DUMMY_SP,
+                name: opening.name.clone(),
+            }),
+        })))),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn jsx_text_whitespace() {
+        assert_eq!(clean_jsx_text("Hello "), "Hello ");
+        assert_eq!(clean_jsx_text("\n    Hello\n    world\n  "), "Hello world");
+        assert_eq!(clean_jsx_text(", you have\n  "), ", you have");
+        assert_eq!(clean_jsx_text("\n  \n"), "");
+    }
+
+    #[test]
+    fn escaped_text() {
+        let mut message = ChildrenMessage::default();
+        message.push_text("It's {not} <b>");
+        assert_eq!(message.message, "It's '{'not'}' '<'b'>'");
+
+        let mut message = ChildrenMessage::default();
+        message.push_text("Rock 'n'");
+        assert_eq!(message.message, "Rock 'n''");
+    }
+
+    #[test]
+    fn value_names() {
+        let mut message = ChildrenMessage::default();
+        let ident = |name: &str| Expr::Ident(Ident::new(name.into(), DUMMY_SP));
+
+        assert_eq!(message.add_value("name", ident("name")), "name");
+        assert_eq!(message.add_value("name", ident("name")), "name");
+        assert_eq!(message.add_value("name", ident("other")), "name2");
+        assert_eq!(message.add_value("name", ident("another")), "name3");
+    }
+}
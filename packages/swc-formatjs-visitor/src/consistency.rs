@@ -0,0 +1,589 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use icu_messageformat_parser::{
+    Ast, AstElement, ErrorKind, Parser, ParserOptions, PluralType, Span,
+};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::ExtractedMessage;
+
+/// Type of an argument as used by the formatter at runtime.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArgumentType {
+    Argument,
+    Number,
+    Date,
+    Time,
+    Select,
+    Plural,
+    SelectOrdinal,
+}
+
+impl ArgumentType {
+    /// Whether a translation may use `other` where the source message uses `self`
+    /// without breaking at runtime. Simple arguments can be freely formatted as
+    /// number / date / time, but select and plural arguments must stay as is.
+    fn is_compatible_with(self, other: ArgumentType) -> bool {
+        use ArgumentType::*;
+
+        match (self, other) {
+            (a, b) if a == b => true,
+            (Argument | Number | Date | Time, Argument) => true,
+            (Argument, Number | Date | Time) => true,
+            (Date, Time) | (Time, Date) => true,
+            _ => false,
+        }
+    }
+
+    /// How much the type constrains the value, to compare arguments used more than once
+    /// by their most specific use, i.e. `{n}` and `{n, plural, ...}` as a plural.
+    fn specificity(self) -> u8 {
+        use ArgumentType::*;
+
+        match self {
+            Argument => 0,
+            Number | Date | Time => 1,
+            Select | Plural | SelectOrdinal => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TranslationIssueKind {
+    /// The source `defaultMessage` itself failed to parse.
+    #[serde(rename_all = "camelCase")]
+    InvalidSourceMessage { error: ErrorKind },
+    /// The translated message failed to parse.
+    #[serde(rename_all = "camelCase")]
+    InvalidTranslation { error: ErrorKind },
+    /// An argument of the source message is not used by the translation.
+    #[serde(rename_all = "camelCase")]
+    MissingArgument { name: String },
+    /// The translation uses an argument which does not exist in the source message.
+    #[serde(rename_all = "camelCase")]
+    UnknownArgument { name: String },
+    /// The translation uses an argument with a type incompatible with the source message.
+    #[serde(rename_all = "camelCase")]
+    ArgumentTypeMismatch {
+        name: String,
+        expected: ArgumentType,
+        found: ArgumentType,
+    },
+    /// A rich text tag of the source message is not used by the translation.
+    #[serde(rename_all = "camelCase")]
+    MissingTag { name: String },
+    /// The translation uses a rich text tag which does not exist in the source message.
+    #[serde(rename_all = "camelCase")]
+    UnknownTag { name: String },
+    /// A `select` argument of the translation does not cover a key of the source message.
+    #[serde(rename_all = "camelCase")]
+    MissingSelectKey { name: String, key: String },
+    /// A `plural` or `selectordinal` argument uses a category the target locale does not have.
+    #[serde(rename_all = "camelCase")]
+    InvalidPluralCategory { name: String, category: String },
+    /// The translation contains a `#` outside of a plural argument, which will be rendered
+    /// literally instead of being replaced with the count.
+    UnexpectedPound,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationIssue {
+    pub id: String,
+    #[serde(flatten)]
+    pub kind: TranslationIssueKind,
+    /// Location of the offending element. Points into the translation, except for
+    /// `InvalidSourceMessage` and `Missing*` issues where the element only exists in
+    /// the source message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<Span>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationReport {
+    pub locale: String,
+    pub issues: Vec<TranslationIssue>,
+}
+
+impl TranslationReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Structural information of a parsed message required to compare it against another one.
+#[derive(Debug, Default)]
+struct MessageShape {
+    arguments: BTreeMap<String, (ArgumentType, Option<Span>)>,
+    tags: BTreeMap<String, Option<Span>>,
+    select_keys: BTreeMap<String, (BTreeSet<String>, Option<Span>)>,
+    plural_keys: Vec<(String, PluralType, String, Option<Span>)>,
+    stray_pounds: Vec<Option<Span>>,
+}
+
+impl MessageShape {
+    fn from_ast(ast: &Ast) -> Self {
+        let mut shape = MessageShape::default();
+        shape.collect(ast, false);
+        shape
+    }
+
+    /// Record the argument, keeping its most specific type if it's used more than once.
+    fn add_argument(&mut self, name: &str, arg_type: ArgumentType, span: &Option<Span>) {
+        let argument = self
+            .arguments
+            .entry(name.to_string())
+            .or_insert((arg_type, *span));
+        if arg_type.specificity() > argument.0.specificity() {
+            *argument = (arg_type, *span);
+        }
+    }
+
+    fn collect(&mut self, ast: &Ast, in_plural: bool) {
+        for element in ast {
+            match element {
+                AstElement::Literal { value, span } => {
+                    if !in_plural {
+                        for _ in value.matches('#') {
+                            self.stray_pounds.push(*span);
+                        }
+                    }
+                }
+                AstElement::Argument { value, span } => {
+                    self.add_argument(value, ArgumentType::Argument, span)
+                }
+                AstElement::Number { value, span, .. } => {
+                    self.add_argument(value, ArgumentType::Number, span)
+                }
+                AstElement::Date { value, span, .. } => {
+                    self.add_argument(value, ArgumentType::Date, span)
+                }
+                AstElement::Time { value, span, .. } => {
+                    self.add_argument(value, ArgumentType::Time, span)
+                }
+                AstElement::Select {
+                    value,
+                    span,
+                    options,
+                } => {
+                    self.add_argument(value, ArgumentType::Select, span);
+                    self.select_keys
+                        .entry(value.to_string())
+                        .or_insert((Default::default(), *span))
+                        .0
+                        .extend(options.0.iter().map(|(key, _)| key.to_string()));
+                    for (_, option) in &options.0 {
+                        self.collect(&option.value, in_plural);
+                    }
+                }
+                AstElement::Plural {
+                    value,
+                    plural_type,
+                    span,
+                    options,
+                    ..
+                } => {
+                    let arg_type = match plural_type {
+                        PluralType::Cardinal => ArgumentType::Plural,
+                        PluralType::Ordinal => ArgumentType::SelectOrdinal,
+                    };
+                    self.add_argument(value, arg_type, span);
+                    for (key, option) in &options.0 {
+                        self.plural_keys.push((
+                            value.to_string(),
+                            plural_type.clone(),
+                            key.to_string(),
+                            option.location.or(*span),
+                        ));
+                        self.collect(&option.value, true);
+                    }
+                }
+                AstElement::Pound(..) => {}
+                AstElement::Tag {
+                    value,
+                    span,
+                    children,
+                } => {
+                    self.tags.entry(value.to_string()).or_insert(*span);
+                    self.collect(children, in_plural);
+                }
+            }
+        }
+    }
+}
+
+/// Names of the arguments and of the rich text tags of a message.
+pub fn get_placeholder_names(ast: &Ast) -> (BTreeSet<String>, BTreeSet<String>) {
+    let shape = MessageShape::from_ast(ast);
+    (
+        shape.arguments.into_keys().collect(),
+        shape.tags.into_keys().collect(),
+    )
+}
+
+/// Returns the plural categories of the given locale as defined in CLDR, or `None` if the
+/// locale is not known. Only the language subtag is taken into account.
+pub fn get_plural_categories(
+    locale: &str,
+    plural_type: &PluralType,
+) -> Option<&'static [&'static str]> {
+    let language = locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let (cardinal, ordinal): (&[&str], &[&str]) = match language.as_str() {
+        "ja" | "zh" | "ko" | "th" | "id" | "lo" | "my" | "yue" => (&["other"], &["other"]),
+        "vi" | "ms" => (&["other"], &["one", "other"]),
+        "en" => (&["one", "other"], &["one", "two", "few", "other"]),
+        "de" | "nl" | "da" | "fi" | "et" | "el" | "tr" | "bg" | "nb" | "no" | "nn" | "is"
+        | "eu" | "gl" => (&["one", "other"], &["other"]),
+        "sv" | "hu" => (&["one", "other"], &["one", "other"]),
+        "hi" | "bn" | "gu" => (&["one", "other"], &["one", "two", "few", "many", "other"]),
+        "fr" => (&["one", "many", "other"], &["one", "other"]),
+        "es" | "pt" => (&["one", "many", "other"], &["other"]),
+        "it" => (&["one", "many", "other"], &["many", "other"]),
+        "ca" => (&["one", "many", "other"], &["one", "two", "few", "other"]),
+        "ro" => (&["one", "few", "other"], &["one", "other"]),
+        "hr" | "sr" | "bs" => (&["one", "few", "other"], &["other"]),
+        "lv" => (&["zero", "one", "other"], &["other"]),
+        "he" => (&["one", "two", "other"], &["other"]),
+        "sl" => (&["one", "two", "few", "other"], &["other"]),
+        "ru" | "pl" | "cs" | "sk" | "lt" => (&["one", "few", "many", "other"], &["other"]),
+        "be" => (&["one", "few", "many", "other"], &["few", "other"]),
+        "uk" => (&["one", "few", "many", "other"], &["few", "other"]),
+        "ga" => (&["one", "two", "few", "many", "other"], &["one", "other"]),
+        "ar" => (&["zero", "one", "two", "few", "many", "other"], &["other"]),
+        "cy" => (
+            &["zero", "one", "two", "few", "many", "other"],
+            &["zero", "one", "two", "few", "many", "other"],
+        ),
+        _ => return None,
+    };
+
+    Some(match plural_type {
+        PluralType::Cardinal => cardinal,
+        PluralType::Ordinal => ordinal,
+    })
+}
+
+static PARSER_OPTIONS: Lazy<ParserOptions> = Lazy::new(|| ParserOptions {
+    capture_location: true,
+    ..Default::default()
+});
+
+fn check_translation(
+    id: &str,
+    source_message: &str,
+    translation: &str,
+    locale: &str,
+    issues: &mut Vec<TranslationIssue>,
+) {
+    let issue = |kind: TranslationIssueKind, location: Option<Span>| TranslationIssue {
+        id: id.to_string(),
+        kind,
+        location,
+    };
+
+    let source = match Parser::new(source_message, &PARSER_OPTIONS).parse() {
+        Ok(ast) => MessageShape::from_ast(&ast),
+        Err(e) => {
+            issues.push(issue(
+                TranslationIssueKind::InvalidSourceMessage { error: e.kind },
+                e.location,
+            ));
+            return;
+        }
+    };
+
+    let target = match Parser::new(translation, &PARSER_OPTIONS).parse() {
+        Ok(ast) => MessageShape::from_ast(&ast),
+        Err(e) => {
+            issues.push(issue(
+                TranslationIssueKind::InvalidTranslation { error: e.kind },
+                e.location,
+            ));
+            return;
+        }
+    };
+
+    for (name, (expected, span)) in &source.arguments {
+        match target.arguments.get(name) {
+            None => issues.push(issue(
+                TranslationIssueKind::MissingArgument { name: name.clone() },
+                *span,
+            )),
+            Some((found, span)) if !expected.is_compatible_with(*found) => issues.push(issue(
+                TranslationIssueKind::ArgumentTypeMismatch {
+                    name: name.clone(),
+                    expected: *expected,
+                    found: *found,
+                },
+                *span,
+            )),
+            _ => {}
+        }
+    }
+
+    for (name, (_, span)) in &target.arguments {
+        if !source.arguments.contains_key(name) {
+            issues.push(issue(
+                TranslationIssueKind::UnknownArgument { name: name.clone() },
+                *span,
+            ));
+        }
+    }
+
+    for (name, span) in &source.tags {
+        if !target.tags.contains_key(name) {
+            issues.push(issue(
+                TranslationIssueKind::MissingTag { name: name.clone() },
+                *span,
+            ));
+        }
+    }
+
+    for (name, span) in &target.tags {
+        if !source.tags.contains_key(name) {
+            issues.push(issue(
+                TranslationIssueKind::UnknownTag { name: name.clone() },
+                *span,
+            ));
+        }
+    }
+
+    for (name, (source_keys, _)) in &source.select_keys {
+        if let Some((target_keys, span)) = target.select_keys.get(name) {
+            for key in source_keys.difference(target_keys) {
+                issues.push(issue(
+                    TranslationIssueKind::MissingSelectKey {
+                        name: name.clone(),
+                        key: key.clone(),
+                    },
+                    *span,
+                ));
+            }
+        }
+    }
+
+    for (name, plural_type, key, span) in &target.plural_keys {
+        // Exact matches (`=0`) are always valid regardless of the locale.
+        if key.starts_with('=') {
+            continue;
+        }
+
+        if let Some(categories) = get_plural_categories(locale, plural_type) {
+            if !categories.contains(&key.as_str()) {
+                issues.push(issue(
+                    TranslationIssueKind::InvalidPluralCategory {
+                        name: name.clone(),
+                        category: key.clone(),
+                    },
+                    *span,
+                ));
+            }
+        }
+    }
+
+    if target.stray_pounds.len() > source.stray_pounds.len() {
+        for span in &target.stray_pounds[source.stray_pounds.len()..] {
+            issues.push(issue(TranslationIssueKind::UnexpectedPound, *span));
+        }
+    }
+}
+
+/// Compare translations of a single locale against the source messages they are
+/// translated from.
+///
+/// Translations without a matching source message are ignored, as well as source messages
+/// without translation.
+pub fn check_translations(
+    locale: &str,
+    source_messages: &[ExtractedMessage],
+    translations: &HashMap<String, String>,
+) -> TranslationReport {
+    let mut issues = vec![];
+
+    for message in source_messages {
+        if let Some(translation) = translations.get(&message.id) {
+            check_translation(
+                &message.id,
+                &message.default_message,
+                translation,
+                locale,
+                &mut issues,
+            );
+        }
+    }
+
+    TranslationReport {
+        locale: locale.to_string(),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(source: &str, translation: &str, locale: &str) -> Vec<TranslationIssueKind> {
+        let source_messages = vec![ExtractedMessage {
+            id: "foo".to_string(),
+            default_message: source.to_string(),
+            ..Default::default()
+        }];
+        let translations = HashMap::from([("foo".to_string(), translation.to_string())]);
+
+        check_translations(locale, &source_messages, &translations)
+            .issues
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect()
+    }
+
+    #[test]
+    fn consistent_translation() {
+        assert_eq!(
+            check(
+                "Hello <b>{name}</b>, you have {count, plural, one {# item} other {# items}}",
+                "Bonjour <b>{name}</b>, vous avez {count, plural, one {# article} many {# articles} other {# articles}}",
+                "fr"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn missing_and_unknown_arguments() {
+        assert_eq!(
+            check("Hello {name}", "Hallo {nme}", "de"),
+            vec![
+                TranslationIssueKind::MissingArgument {
+                    name: "name".to_string()
+                },
+                TranslationIssueKind::UnknownArgument {
+                    name: "nme".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn argument_type_mismatch() {
+        assert_eq!(
+            check("{count, number} items", "{count, number} Artikel", "de"),
+            vec![]
+        );
+        assert_eq!(
+            check(
+                "{gender, select, male {He} other {They}}",
+                "{gender, number}",
+                "de"
+            ),
+            vec![TranslationIssueKind::ArgumentTypeMismatch {
+                name: "gender".to_string(),
+                expected: ArgumentType::Select,
+                found: ArgumentType::Number,
+            }]
+        );
+    }
+
+    #[test]
+    fn renamed_tag() {
+        assert_eq!(
+            check("Hello <b>world</b>", "Hallo <strong>Welt</strong>", "de"),
+            vec![
+                TranslationIssueKind::MissingTag {
+                    name: "b".to_string()
+                },
+                TranslationIssueKind::UnknownTag {
+                    name: "strong".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn select_keys_and_plural_categories() {
+        assert_eq!(
+            check(
+                "{gender, select, male {He} female {She} other {They}}",
+                "{gender, select, male {Il} other {Iel}}",
+                "fr"
+            ),
+            vec![TranslationIssueKind::MissingSelectKey {
+                name: "gender".to_string(),
+                key: "female".to_string(),
+            }]
+        );
+        assert_eq!(
+            check(
+                "{count, plural, one {# item} other {# items}}",
+                "{count, plural, =0 {なし} one {#} other {#}}",
+                "ja"
+            ),
+            vec![TranslationIssueKind::InvalidPluralCategory {
+                name: "count".to_string(),
+                category: "one".to_string(),
+            }]
+        );
+        assert_eq!(
+            check(
+                "{place, selectordinal, one {#st} two {#nd} few {#rd} other {#th}}",
+                "{place, selectordinal, one {#-ы} few {#-і} other {#-ы}}",
+                "be"
+            ),
+            vec![TranslationIssueKind::InvalidPluralCategory {
+                name: "place".to_string(),
+                category: "one".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reordered_arguments() {
+        assert_eq!(
+            check(
+                "{n} of {n, plural, one {# item} other {# items}}",
+                "{n, plural, one {# Artikel} other {# Artikel}} von {n}",
+                "de"
+            ),
+            vec![]
+        );
+        assert_eq!(
+            check(
+                "{n} of {n, plural, one {# item} other {# items}}",
+                "{n, number} von {n}",
+                "de"
+            ),
+            vec![TranslationIssueKind::ArgumentTypeMismatch {
+                name: "n".to_string(),
+                expected: ArgumentType::Plural,
+                found: ArgumentType::Number,
+            }]
+        );
+    }
+
+    #[test]
+    fn unexpected_pound_and_parse_errors() {
+        assert_eq!(
+            check("Item number", "Artikel #", "de"),
+            vec![TranslationIssueKind::UnexpectedPound]
+        );
+        assert_eq!(
+            check("Item # of #", "Artikel # von # (#)", "de"),
+            vec![TranslationIssueKind::UnexpectedPound]
+        );
+        assert_eq!(
+            check("Hello {name}", "Hallo {name", "de"),
+            vec![TranslationIssueKind::InvalidTranslation {
+                error: ErrorKind::ExpectArgumentClosingBrace
+            }]
+        );
+    }
+}
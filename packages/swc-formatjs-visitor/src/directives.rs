@@ -0,0 +1,332 @@
+use std::collections::{BTreeMap, HashSet};
+
+use swc_core::{
+    common::{
+        comments::{Comment, Comments},
+        BytePos, SourceMapper, Span,
+    },
+    ecma::{
+        ast::{JSXExprContainer, ModuleItem},
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+/// Skips the descriptor starting on the line following the comment.
+static IGNORE_NEXT_LINE: &str = "formatjs-ignore-next-line";
+/// Skips descriptors until `formatjs-enable`, or the end of the file.
+static DISABLE: &str = "formatjs-disable";
+static ENABLE: &str = "formatjs-enable";
+/// Overrides plugin options for the file, i.e. `@formatjs preserveWhitespace`.
+static OPTIONS: &str = "@formatjs";
+
+/// Options which can be overridden per file with `@formatjs` comments. Options deriving
+/// state of the visitor, like `additionalFunctionNames`, are configured for all files.
+pub static OVERRIDABLE_OPTIONS: &[&str] = &[
+    "removeDefaultMessage",
+    "idInterpolationPattern",
+    "idInterpolatePattern",
+    "idInterpolateRoot",
+    "ast",
+    "extractSourceLocation",
+    "preserveWhitespace",
+];
+
+/// A plugin option set by a `@formatjs` comment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionOverride {
+    pub key: String,
+    /// `None` for a bare key, which enables a boolean option.
+    pub value: Option<String>,
+    pub span: Span,
+}
+
+impl OptionOverride {
+    /// The JSON value of the option: values are parsed as JSON if possible (`false`, `10`),
+    /// otherwise used as string.
+    pub fn json_value(&self) -> serde_json::Value {
+        match &self.value {
+            None => serde_json::Value::Bool(true),
+            Some(value) => serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.clone())),
+        }
+    }
+}
+
+/// Comment directives of a file honoured by the visitor.
+#[derive(Debug, Clone, Default)]
+pub struct FileDirectives {
+    /// Lines following `formatjs-ignore-next-line` comments.
+    ignored_lines: HashSet<usize>,
+    /// Regions between `formatjs-disable` and `formatjs-enable` comments.
+    disabled_ranges: Vec<(BytePos, BytePos)>,
+    pub option_overrides: Vec<OptionOverride>,
+}
+
+/// Collect the comments attached to any node of the module, ordered by position.
+pub fn collect_comments<C: Comments>(items: &[ModuleItem], comments: &C) -> Vec<Comment> {
+    let mut collector = CommentCollector {
+        comments,
+        collected: Default::default(),
+    };
+    for item in items {
+        item.visit_with(&mut collector);
+    }
+
+    collector.collected.into_values().collect()
+}
+
+impl FileDirectives {
+    /// Collect the directives of the comments.
+    pub fn collect<S: SourceMapper>(comments: &[Comment], source_map: &S) -> Self {
+        let mut directives = FileDirectives::default();
+        let mut disabled_from = None;
+
+        for comment in comments {
+            let text = comment_text(comment);
+            let mut words = text.split_whitespace();
+
+            match words.next() {
+                Some(word) if word == IGNORE_NEXT_LINE => {
+                    let line = source_map.lookup_char_pos(comment.span.hi).line;
+                    directives.ignored_lines.insert(line + 1);
+                }
+                Some(word) if word == DISABLE => {
+                    disabled_from.get_or_insert(comment.span.hi);
+                }
+                Some(word) if word == ENABLE => {
+                    if let Some(from) = disabled_from.take() {
+                        directives.disabled_ranges.push((from, comment.span.lo));
+                    }
+                }
+                Some(word) if word == OPTIONS => {
+                    directives
+                        .option_overrides
+                        .extend(words.map(|entry| parse_option_override(entry, comment.span)));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(from) = disabled_from {
+            directives.disabled_ranges.push((from, BytePos(u32::MAX)));
+        }
+
+        directives
+    }
+
+    /// Whether a descriptor starting at `pos` on `line` is excluded by a directive.
+    pub fn is_ignored(&self, pos: BytePos, line: usize) -> bool {
+        self.ignored_lines.contains(&line)
+            || self
+                .disabled_ranges
+                .iter()
+                .any(|(from, to)| *from <= pos && pos < *to)
+    }
+}
+
+/// The description of the last translator comment leading the node at `pos`, i.e.
+/// `// i18n: Shown on the checkout button`.
+pub fn comment_description<C: Comments>(
+    comments: &C,
+    pos: BytePos,
+    markers: &[String],
+) -> Option<String> {
+    comments
+        .get_leading(pos)
+        .unwrap_or_default()
+        .iter()
+        .rev()
+        .find_map(|comment| get_comment_description(comment, markers))
+}
+
+/// The text following the first of the markers in the comment, up to the next JSDoc tag,
+/// with line decorations and whitespace collapsed.
+fn get_comment_description(comment: &Comment, markers: &[String]) -> Option<String> {
+    let text = &*comment.text;
+    let start = markers
+        .iter()
+        .filter(|marker| !marker.is_empty())
+        .filter_map(|marker| {
+            text.match_indices(marker.as_str())
+                .find(|(index, _)| {
+                    text[..*index]
+                        .chars()
+                        .next_back()
+                        .is_none_or(|c| c.is_whitespace() || c == '*')
+                })
+                .map(|(index, marker)| index + marker.len())
+        })
+        .min()?;
+
+    let mut words = vec![];
+    for (idx, line) in text[start..].lines().enumerate() {
+        let line = if idx == 0 {
+            line
+        } else {
+            line.trim_start().trim_start_matches('*')
+        };
+        if idx > 0 && line.trim_start().starts_with('@') {
+            break;
+        }
+        words.extend(line.split_whitespace());
+    }
+
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join(" "))
+    }
+}
+
+/// Text of the comment without the leading `*`s of doc block comments.
+fn comment_text(comment: &Comment) -> &str {
+    comment.text.trim().trim_start_matches('*').trim()
+}
+
+/// Parse `key`, `key=value` or `key:value` of a `@formatjs` comment.
+fn parse_option_override(entry: &str, span: Span) -> OptionOverride {
+    match entry.find(['=', ':']) {
+        Some(index) => OptionOverride {
+            key: entry[..index].to_string(),
+            value: Some(entry[index + 1..].to_string()),
+            span,
+        },
+        None => OptionOverride {
+            key: entry.to_string(),
+            value: None,
+            span,
+        },
+    }
+}
+
+/// Collects the comments attached to any node, ordered by position.
+struct CommentCollector<'a, C: Comments> {
+    comments: &'a C,
+    collected: BTreeMap<BytePos, Comment>,
+}
+
+impl<C: Comments> CommentCollector<'_, C> {
+    fn collect(&mut self, comments: Option<Vec<Comment>>) {
+        for comment in comments.unwrap_or_default() {
+            self.collected.entry(comment.span.lo).or_insert(comment);
+        }
+    }
+}
+
+impl<C: Comments> Visit for CommentCollector<'_, C> {
+    noop_visit_type!();
+
+    fn visit_span(&mut self, span: &Span) {
+        if span.is_dummy() {
+            return;
+        }
+
+        self.collect(self.comments.get_leading(span.lo));
+        self.collect(self.comments.get_trailing(span.hi));
+    }
+
+    fn visit_jsx_expr_container(&mut self, container: &JSXExprContainer) {
+        container.visit_children_with(self);
+
+        // Comments of `{/* ... */}` are attached to the opening brace.
+        if !container.span.is_dummy() {
+            self.collect(self.comments.get_trailing(container.span.lo + BytePos(1)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::common::{
+        comments::{CommentKind, SingleThreadedComments},
+        DUMMY_SP,
+    };
+
+    use super::*;
+
+    #[test]
+    fn option_overrides() {
+        let preserve_whitespace = parse_option_override("preserveWhitespace", DUMMY_SP);
+        assert_eq!(preserve_whitespace.value, None);
+        assert_eq!(preserve_whitespace.json_value(), serde_json::json!(true));
+
+        let pattern = parse_option_override(
+            "idInterpolationPattern=[sha512:contenthash:base64:6]",
+            DUMMY_SP,
+        );
+        assert_eq!(pattern.key, "idInterpolationPattern");
+        assert_eq!(
+            pattern.json_value(),
+            serde_json::json!("[sha512:contenthash:base64:6]")
+        );
+
+        let ast = parse_option_override("ast:false", DUMMY_SP);
+        assert_eq!(ast.key, "ast");
+        assert_eq!(ast.json_value(), serde_json::json!(false));
+    }
+
+    #[test]
+    fn description_comments() {
+        let markers = ["i18n:".to_string(), "@description".to_string()];
+        let description = |text: &str| {
+            get_comment_description(
+                &Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: text.into(),
+                },
+                &markers,
+            )
+        };
+
+        assert_eq!(
+            description(" i18n: Shown on the  checkout button"),
+            Some("Shown on the checkout button".to_string())
+        );
+        assert_eq!(
+            description("*\n * Greeting.\n * @description Shown\n * on top\n * @see x\n "),
+            Some("Shown on top".to_string())
+        );
+        assert_eq!(description(" Shown on the checkout button"), None);
+        assert_eq!(description(" noi18n: foo"), None);
+        assert_eq!(description(" i18n: "), None);
+    }
+
+    #[test]
+    fn leading_description_comments() {
+        let comments = SingleThreadedComments::default();
+        let comment = |text: &str, lo: u32| Comment {
+            kind: CommentKind::Line,
+            span: Span::new(BytePos(lo), BytePos(lo + 10), Default::default()),
+            text: text.into(),
+        };
+        comments.add_leading(BytePos(20), comment(" i18n: Checkout button", 0));
+        comments.add_leading(BytePos(20), comment(" i18n: Shown on top", 10));
+        comments.add_leading(BytePos(40), comment(" Shown on top", 30));
+        comments.add_trailing(BytePos(60), comment(" i18n: Shown on top", 60));
+
+        let markers = ["i18n:".to_string()];
+        assert_eq!(
+            comment_description(&comments, BytePos(20), &markers),
+            Some("Shown on top".to_string())
+        );
+        assert_eq!(comment_description(&comments, BytePos(40), &markers), None);
+        assert_eq!(comment_description(&comments, BytePos(60), &markers), None);
+        assert_eq!(comment_description(&comments, BytePos(20), &[]), None);
+    }
+
+    #[test]
+    fn ignored_positions() {
+        let directives = FileDirectives {
+            ignored_lines: [3].into_iter().collect(),
+            disabled_ranges: vec![(BytePos(10), BytePos(20))],
+            option_overrides: vec![],
+        };
+
+        assert!(directives.is_ignored(BytePos(0), 3));
+        assert!(directives.is_ignored(BytePos(10), 1));
+        assert!(!directives.is_ignored(BytePos(20), 1));
+        assert!(!directives.is_ignored(BytePos(5), 2));
+    }
+}
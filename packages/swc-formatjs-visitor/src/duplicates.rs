@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{ExtractedMessage, SourceLocation};
+
+/// Where a message was extracted from.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageOrigin {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loc: Option<SourceLocation>,
+}
+
+/// Two messages sharing the same id with a different `defaultMessage` or `description`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageConflict {
+    pub id: String,
+    pub first: MessageOrigin,
+    pub second: MessageOrigin,
+}
+
+/// Two messages with different ids whose `idOnly` short ids are the same.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortIdCollision {
+    pub short_id: String,
+    pub first_id: String,
+    pub first: MessageOrigin,
+    pub second_id: String,
+    pub second: MessageOrigin,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedMessages {
+    /// Extracted messages with identical duplicates removed. For conflicting ids, the first
+    /// extracted message is kept.
+    pub messages: Vec<ExtractedMessage>,
+    pub conflicts: Vec<MessageConflict>,
+    /// Short ids shared by messages of different files. Collisions within a file are
+    /// reported by the transform.
+    pub short_id_collisions: Vec<ShortIdCollision>,
+}
+
+/// Merge messages extracted from multiple files, deduplicating identical messages and
+/// collecting the ones sharing an id with different content.
+///
+/// This also catches collisions of generated ids, i.e. when a short
+/// `[sha512:contenthash:base64:6]` hash of two different messages is the same, and of
+/// `idOnly` short ids of different ids.
+pub fn merge_extracted_messages<'a>(
+    files: impl IntoIterator<Item = (&'a str, &'a [ExtractedMessage])>,
+) -> MergedMessages {
+    let mut ret = MergedMessages::default();
+    let mut origins: Vec<MessageOrigin> = vec![];
+    // Index in `ret.messages` of the message kept for each id, and for each short id.
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut short_id_indices: HashMap<String, usize> = HashMap::new();
+
+    for (file, messages) in files {
+        for message in messages {
+            let origin = MessageOrigin {
+                file: file.to_string(),
+                loc: message.loc.clone(),
+            };
+
+            let existing = if message.id.is_empty() {
+                None
+            } else {
+                indices.get(&message.id).copied()
+            };
+
+            match existing {
+                Some(index) => {
+                    if !ret.messages[index].has_same_content(message) {
+                        ret.conflicts.push(MessageConflict {
+                            id: message.id.clone(),
+                            first: origins[index].clone(),
+                            second: origin,
+                        });
+                    }
+                }
+                None => {
+                    if !message.id.is_empty() {
+                        indices.insert(message.id.clone(), ret.messages.len());
+                    }
+                    if let Some(short_id) = &message.short_id {
+                        match short_id_indices.get(short_id) {
+                            // Reported by the transform of the file.
+                            Some(&index) if origins[index].file == file => {}
+                            Some(&index) => ret.short_id_collisions.push(ShortIdCollision {
+                                short_id: short_id.clone(),
+                                first_id: ret.messages[index].id.clone(),
+                                first: origins[index].clone(),
+                                second_id: message.id.clone(),
+                                second: origin.clone(),
+                            }),
+                            None => {
+                                short_id_indices.insert(short_id.clone(), ret.messages.len());
+                            }
+                        }
+                    }
+                    ret.messages.push(message.clone());
+                    origins.push(origin);
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, default_message: &str) -> ExtractedMessage {
+        ExtractedMessage {
+            id: id.to_string(),
+            default_message: default_message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn dedupe_identical_messages() {
+        let a = [message("foo", "Foo"), message("bar", "Bar")];
+        let b = [message("foo", "Foo")];
+
+        let merged = merge_extracted_messages([("a.js", &a[..]), ("b.js", &b[..])]);
+
+        assert_eq!(
+            merged
+                .messages
+                .iter()
+                .map(|m| m.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["foo", "bar"]
+        );
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn report_conflicting_messages() {
+        let a = [message("foo", "Foo")];
+        let b = [message("bar", "Bar"), message("foo", "Not foo")];
+
+        let merged = merge_extracted_messages([("a.js", &a[..]), ("b.js", &b[..])]);
+
+        assert_eq!(merged.messages.len(), 2);
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].id, "foo");
+        assert_eq!(merged.conflicts[0].first.file, "a.js");
+        assert_eq!(merged.conflicts[0].second.file, "b.js");
+    }
+
+    #[test]
+    fn report_short_id_collisions() {
+        let short = |id: &str, short_id: &str| ExtractedMessage {
+            short_id: Some(short_id.to_string()),
+            ..message(id, "Message")
+        };
+        let a = [short("foo", "x1"), short("bar", "y2"), short("qux", "y2")];
+        let b = [short("foo", "x1"), short("baz", "x1")];
+
+        let merged = merge_extracted_messages([("a.js", &a[..]), ("b.js", &b[..])]);
+
+        // `bar` and `qux` of the same file are reported by the transform.
+        assert_eq!(merged.messages.len(), 4);
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(merged.short_id_collisions.len(), 1);
+        let collision = &merged.short_id_collisions[0];
+        assert_eq!(
+            (collision.first_id.as_str(), collision.first.file.as_str()),
+            ("foo", "a.js")
+        );
+        assert_eq!(
+            (collision.second_id.as_str(), collision.second.file.as_str()),
+            ("baz", "b.js")
+        );
+    }
+}
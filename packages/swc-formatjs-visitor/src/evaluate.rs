@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Number, Value};
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::{
+        ast::{
+            BinExpr, BinaryOp, BindingIdent, ClassDecl, ClassExpr, Expr, FnDecl, FnExpr, Lit,
+            ModuleItem, Pat, Prop, PropName, PropOrSpread, UnaryExpr, UnaryOp, VarDecl,
+            VarDeclKind,
+        },
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+/// Maximum depth of `const` references followed while evaluating an expression,
+/// which also guards against cyclic references.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+#[derive(Debug, Clone)]
+enum StaticValue {
+    Str(String),
+    Num(f64),
+}
+
+/// Largest integer JS numbers represent exactly, `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+/// Convert a number to JSON, keeping safe integers as integers. The JSON number is not
+/// necessarily serialized the way JS does, `stable_stringify` formats it like JS instead.
+fn number_to_json(value: f64) -> Option<Value> {
+    if value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER {
+        Some(Value::Number((value as i64).into()))
+    } else {
+        Number::from_f64(value).map(Value::Number)
+    }
+}
+
+/// Stringify a JSON value with sorted object keys and numbers formatted like JS, the way
+/// `json-stable-stringify` does.
+pub fn stable_stringify(value: &Value) -> String {
+    match value {
+        Value::Array(values) => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(stable_stringify)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+        Value::Object(values) => {
+            let mut entries = values.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            format!(
+                "{{{}}}",
+                entries
+                    .iter()
+                    .map(|(key, value)| format!(
+                        "{}:{}",
+                        serde_json::to_string(key).expect("Should be serializable"),
+                        stable_stringify(value)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            )
+        }
+        Value::Number(number) => match number.as_f64() {
+            Some(number) => number_to_string(number),
+            None => number.to_string(),
+        },
+        value => serde_json::to_string(value).expect("Should be serializable"),
+    }
+}
+
+impl StaticValue {
+    /// Stringify the value the same way JS does for the values we can evaluate.
+    fn into_string(self) -> String {
+        match self {
+            StaticValue::Str(value) => value,
+            StaticValue::Num(value) => number_to_string(value),
+        }
+    }
+}
+
+/// Port of JS `Number::toString` with radix 10, i.e. `String(n)`.
+fn number_to_string(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value < 0.0 {
+        return format!("-{}", number_to_string(-value));
+    }
+    if value.is_infinite() {
+        return "Infinity".to_string();
+    }
+
+    // The shortest digits which round-trip, as in JS, and the exponent `n` of the
+    // spec, where the value is `0.digits * 10^n`.
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("Should be in scientific notation");
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().expect("Should be an integer") + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let exponent = (n - 1).abs();
+        match k {
+            1 => format!("{}e{}{}", digits, sign, exponent),
+            _ => format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, exponent),
+        }
+    }
+}
+
+/// `const` declarations of a module, used to statically evaluate
+/// message descriptor values referencing them.
+#[derive(Debug, Clone, Default)]
+pub struct ConstBindings {
+    consts: HashMap<String, Box<Expr>>,
+    // Number of bindings of each name in the module. We do not run scope analysis, so a
+    // `const` shadowed anywhere in the module is not evaluated to avoid picking the
+    // wrong declaration.
+    binding_counts: HashMap<String, usize>,
+}
+
+impl ConstBindings {
+    fn count_binding(&mut self, name: &str) {
+        *self.binding_counts.entry(name.to_string()).or_default() += 1;
+    }
+}
+
+impl Visit for ConstBindings {
+    noop_visit_type!();
+
+    fn visit_binding_ident(&mut self, ident: &BindingIdent) {
+        self.count_binding(&ident.id.sym);
+    }
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.count_binding(&fn_decl.ident.sym);
+        fn_decl.visit_children_with(self);
+    }
+
+    fn visit_fn_expr(&mut self, fn_expr: &FnExpr) {
+        if let Some(ident) = &fn_expr.ident {
+            self.count_binding(&ident.sym);
+        }
+        fn_expr.visit_children_with(self);
+    }
+
+    fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+        self.count_binding(&class_decl.ident.sym);
+        class_decl.visit_children_with(self);
+    }
+
+    fn visit_class_expr(&mut self, class_expr: &ClassExpr) {
+        if let Some(ident) = &class_expr.ident {
+            self.count_binding(&ident.sym);
+        }
+        class_expr.visit_children_with(self);
+    }
+
+    fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+        var_decl.visit_children_with(self);
+
+        if var_decl.kind != VarDeclKind::Const {
+            return;
+        }
+
+        for decl in &var_decl.decls {
+            if let (Pat::Ident(ident), Some(init)) = (&decl.name, &decl.init) {
+                self.consts.insert(ident.id.sym.to_string(), init.clone());
+            }
+        }
+    }
+}
+
+impl ConstBindings {
+    pub fn collect(items: &[ModuleItem]) -> Self {
+        let mut bindings = ConstBindings::default();
+        for item in items {
+            item.visit_with(&mut bindings);
+        }
+        bindings
+    }
+
+    /// Whether the name is declared anywhere in the module, other than by an import.
+    pub fn is_declared(&self, name: &str) -> bool {
+        self.binding_counts.contains_key(name)
+    }
+
+    pub fn get_const(&self, name: &str) -> Option<&Expr> {
+        if self.binding_counts.get(name) != Some(&1) {
+            return None;
+        }
+        self.consts.get(name).map(|init| &**init)
+    }
+
+    /// Evaluate an expression to a string if it only consists of string / number literals,
+    /// `+` concatenation, template literals, references to `const` bindings and
+    /// TypeScript type assertions. Returns `None` for anything else.
+    pub fn evaluate_string(&self, expr: &Expr) -> Option<String> {
+        self.evaluate(expr, 0).map(StaticValue::into_string)
+    }
+
+    /// Evaluate an expression to a JSON value if it only consists of literals, object and
+    /// array literals (including spreads and shorthand properties), the expressions
+    /// `evaluate_string` supports and references to `const` bindings. Returns the span
+    /// of the first expression which cannot be evaluated otherwise.
+    pub fn evaluate_json(&self, expr: &Expr) -> Result<Value, Span> {
+        self.evaluate_json_value(expr, 0)
+    }
+
+    fn evaluate_json_value(&self, expr: &Expr, depth: usize) -> Result<Value, Span> {
+        if depth > MAX_REFERENCE_DEPTH {
+            return Err(expr.span());
+        }
+
+        match expr {
+            Expr::Lit(Lit::Bool(value)) => Ok(Value::Bool(value.value)),
+            Expr::Lit(Lit::Null(..)) => Ok(Value::Null),
+            Expr::Unary(UnaryExpr {
+                op: UnaryOp::Minus,
+                arg,
+                ..
+            }) => match self.evaluate(arg, depth) {
+                Some(StaticValue::Num(value)) => number_to_json(-value).ok_or(expr.span()),
+                _ => Err(expr.span()),
+            },
+            Expr::Object(obj) => {
+                let mut values = Map::new();
+                for prop in &obj.props {
+                    match prop {
+                        PropOrSpread::Spread(spread) => {
+                            match self.evaluate_json_value(&spread.expr, depth)? {
+                                Value::Object(spread_values) => values.extend(spread_values),
+                                _ => return Err(spread.expr.span()),
+                            }
+                        }
+                        PropOrSpread::Prop(prop) => match &**prop {
+                            Prop::KeyValue(kv) => {
+                                let key = match &kv.key {
+                                    PropName::Ident(ident) => ident.sym.to_string(),
+                                    PropName::Str(str) => str.value.to_string(),
+                                    PropName::Num(num) => StaticValue::Num(num.value).into_string(),
+                                    PropName::Computed(computed) => self
+                                        .evaluate(&computed.expr, depth)
+                                        .ok_or(computed.span)?
+                                        .into_string(),
+                                    PropName::BigInt(big_int) => return Err(big_int.span),
+                                };
+                                values.insert(key, self.evaluate_json_value(&kv.value, depth)?);
+                            }
+                            Prop::Shorthand(ident) => {
+                                let init = self.get_const(&ident.sym).ok_or(ident.span)?;
+                                values.insert(
+                                    ident.sym.to_string(),
+                                    self.evaluate_json_value(init, depth + 1)?,
+                                );
+                            }
+                            _ => return Err(prop.span()),
+                        },
+                    }
+                }
+                Ok(Value::Object(values))
+            }
+            Expr::Array(array) => {
+                let mut values = vec![];
+                for elem in &array.elems {
+                    match elem {
+                        // Holes are stringified as `null`, like `JSON.stringify`.
+                        None => values.push(Value::Null),
+                        Some(elem) if elem.spread.is_some() => {
+                            match self.evaluate_json_value(&elem.expr, depth)? {
+                                Value::Array(spread_values) => values.extend(spread_values),
+                                _ => return Err(elem.expr.span()),
+                            }
+                        }
+                        Some(elem) => values.push(self.evaluate_json_value(&elem.expr, depth)?),
+                    }
+                }
+                Ok(Value::Array(values))
+            }
+            Expr::Paren(paren) => self.evaluate_json_value(&paren.expr, depth),
+            Expr::TsAs(ts_as) => self.evaluate_json_value(&ts_as.expr, depth),
+            Expr::TsConstAssertion(assertion) => self.evaluate_json_value(&assertion.expr, depth),
+            Expr::TsTypeAssertion(assertion) => self.evaluate_json_value(&assertion.expr, depth),
+            Expr::TsNonNull(non_null) => self.evaluate_json_value(&non_null.expr, depth),
+            Expr::Ident(ident) => {
+                let init = self.get_const(&ident.sym).ok_or(ident.span)?;
+                self.evaluate_json_value(init, depth + 1)
+            }
+            _ => match self.evaluate(expr, depth) {
+                Some(StaticValue::Str(value)) => Ok(Value::String(value)),
+                Some(StaticValue::Num(value)) => number_to_json(value).ok_or(expr.span()),
+                None => Err(expr.span()),
+            },
+        }
+    }
+
+    fn evaluate(&self, expr: &Expr, depth: usize) -> Option<StaticValue> {
+        if depth > MAX_REFERENCE_DEPTH {
+            return None;
+        }
+
+        match expr {
+            Expr::Lit(Lit::Str(str)) => Some(StaticValue::Str(str.value.to_string())),
+            Expr::Lit(Lit::Num(num)) => Some(StaticValue::Num(num.value)),
+            Expr::Tpl(tpl) => {
+                let mut ret = String::new();
+                for (idx, quasi) in tpl.quasis.iter().enumerate() {
+                    ret.push_str(quasi.cooked.as_ref()?);
+                    if let Some(expr) = tpl.exprs.get(idx) {
+                        ret.push_str(&self.evaluate(expr, depth)?.into_string());
+                    }
+                }
+                Some(StaticValue::Str(ret))
+            }
+            Expr::Bin(BinExpr {
+                op: BinaryOp::Add,
+                left,
+                right,
+                ..
+            }) => {
+                let left = self.evaluate(left, depth)?;
+                let right = self.evaluate(right, depth)?;
+                Some(match (left, right) {
+                    (StaticValue::Num(left), StaticValue::Num(right)) => {
+                        StaticValue::Num(left + right)
+                    }
+                    (left, right) => StaticValue::Str(left.into_string() + &right.into_string()),
+                })
+            }
+            Expr::Paren(paren) => self.evaluate(&paren.expr, depth),
+            Expr::TsAs(ts_as) => self.evaluate(&ts_as.expr, depth),
+            Expr::TsConstAssertion(assertion) => self.evaluate(&assertion.expr, depth),
+            Expr::TsTypeAssertion(assertion) => self.evaluate(&assertion.expr, depth),
+            Expr::TsNonNull(non_null) => self.evaluate(&non_null.expr, depth),
+            Expr::Ident(ident) => {
+                let init = self.get_const(&ident.sym)?;
+                self.evaluate(init, depth + 1)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use swc_core::{
+        common::{FileName, SourceMap},
+        ecma::parser::{parse_file_as_expr, parse_file_as_module},
+    };
+
+    use super::*;
+
+    /// Evaluate `expr` with the bindings of the module `src`.
+    fn evaluate_string(src: &str, expr: &str) -> Option<String> {
+        evaluate_with(src, expr, |bindings, expr| bindings.evaluate_string(expr))
+    }
+
+    fn evaluate_json(src: &str, expr: &str) -> Option<Value> {
+        evaluate_with(src, expr, |bindings, expr| {
+            bindings.evaluate_json(expr).ok()
+        })
+    }
+
+    fn evaluate_with<T>(
+        src: &str,
+        expr: &str,
+        evaluate: impl Fn(&ConstBindings, &Expr) -> Option<T>,
+    ) -> Option<T> {
+        let source_map = SourceMap::default();
+        let module = parse_file_as_module(
+            &source_map.new_source_file(FileName::Anon, src.into()),
+            Default::default(),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .expect("Should parse the module");
+        let expr = parse_file_as_expr(
+            &source_map.new_source_file(FileName::Anon, expr.into()),
+            Default::default(),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .expect("Should parse the expression");
+
+        evaluate(&ConstBindings::collect(&module.body), &expr)
+    }
+
+    #[test]
+    fn concatenation() {
+        let src = "const greeting = 'Hello'; const name = greeting + ', ' + 'world';";
+
+        assert_eq!(
+            evaluate_string(src, "name + '!'"),
+            Some("Hello, world!".into())
+        );
+        assert_eq!(evaluate_string(src, "1 + 2 + 'px'"), Some("3px".into()));
+        assert_eq!(evaluate_string(src, "'px' + 1 + 2"), Some("px12".into()));
+        assert_eq!(evaluate_string(src, "greeting + unknown"), None);
+    }
+
+    #[test]
+    fn templates() {
+        let src = "const count = 3; const items = `${count} items`;";
+
+        assert_eq!(
+            evaluate_string(src, "`You have ${items} in ${'your' + ' cart'}`"),
+            Some("You have 3 items in your cart".into())
+        );
+        assert_eq!(evaluate_string(src, "`${count * 2} items`"), None);
+    }
+
+    #[test]
+    fn numbers() {
+        let cases = [
+            ("0", "0"),
+            ("42", "42"),
+            ("1.5", "1.5"),
+            ("0.1 + 0.2", "0.30000000000000004"),
+            ("123456789012345680000", "123456789012345680000"),
+            ("1e21", "1e+21"),
+            ("1.5e300", "1.5e+300"),
+            ("0.000001", "0.000001"),
+            ("1e-7", "1e-7"),
+            ("1.25e-10", "1.25e-10"),
+            ("9007199254740993", "9007199254740992"),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(
+                evaluate_string("", &format!("'' + ({})", expr)),
+                Some(expected.to_string()),
+                "{}",
+                expr
+            );
+        }
+
+        assert_eq!(number_to_string(-0.0), "0");
+        assert_eq!(number_to_string(-1e21), "-1e+21");
+        assert_eq!(number_to_string(f64::NAN), "NaN");
+        assert_eq!(number_to_string(f64::NEG_INFINITY), "-Infinity");
+    }
+
+    #[test]
+    fn shadowing() {
+        let src = "
+const label = 'Label';
+const shadowedByFunction = 'Outer';
+const shadowedByClass = 'Outer';
+const shadowedByParam = 'Outer';
+function render(shadowedByParam) {
+  function shadowedByFunction() {}
+  class shadowedByClass {}
+}
+";
+
+        assert_eq!(evaluate_string(src, "label"), Some("Label".into()));
+        assert_eq!(evaluate_string(src, "shadowedByFunction"), None);
+        assert_eq!(evaluate_string(src, "shadowedByClass"), None);
+        assert_eq!(evaluate_string(src, "shadowedByParam"), None);
+    }
+
+    #[test]
+    fn json_values() {
+        let src = "
+const maxLength = 40;
+const base = {context: 'checkout', tags: ['button']};
+const screenshots = ['a.png', , 'b.png'];
+";
+
+        let value = evaluate_json(
+            src,
+            "{...base, maxLength, screenshots: [...screenshots], nested: {z: 1e21, a: -1.5, \
+             m: [1e-7, 1152921504606846976, true, null]}, ['b']: '\"quoted\"', 1: 'one'}",
+        )
+        .expect("Should evaluate");
+        assert_eq!(
+            value,
+            json!({
+                "context": "checkout",
+                "tags": ["button"],
+                "maxLength": 40,
+                "screenshots": ["a.png", null, "b.png"],
+                "nested": {"z": 1e21, "a": -1.5, "m": [1e-7, 1152921504606846976.0, true, null]},
+                "b": "\"quoted\"",
+                "1": "one"
+            })
+        );
+
+        assert_eq!(evaluate_json(src, "{maxLength: unknown}"), None);
+        assert_eq!(evaluate_json(src, "{...maxLength}"), None);
+        assert_eq!(evaluate_json(src, "[...base]"), None);
+        assert_eq!(evaluate_json(src, "{n: 2 ** 60}"), None);
+    }
+
+    #[test]
+    fn stable_stringified_json() {
+        let value = evaluate_json(
+            "const base = {context: 'checkout', tags: ['button']};",
+            "{...base, maxLength: 40, screenshots: ['a.png', , 'b.png'], nested: {z: 1e21, \
+             a: -1.5, m: [1e-7, 1152921504606846976, true, null]}, b: '\"quoted\"'}",
+        )
+        .expect("Should evaluate");
+
+        // `json-stable-stringify` of the same object literal in JS.
+        assert_eq!(
+            stable_stringify(&value),
+            r#"{"b":"\"quoted\"","context":"checkout","maxLength":40,"nested":{"a":-1.5,"m":[1e-7,1152921504606847000,true,null],"z":1e+21},"screenshots":["a.png",null,"b.png"],"tags":["button"]}"#
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use serde::Serialize;
+use swc_core::ecma::ast::{
+    Expr, JSXAttrName, JSXElementName, JSXMemberExpr, JSXNamespacedName, JSXObject, Lit,
+};
+
+use crate::SourceLocation;
+
+/// Attributes of JSX elements checked for hardcoded strings by default.
+pub static DEFAULT_CHECKED_ATTRIBUTES: &[&str] = &["title", "alt", "placeholder", "aria-label"];
+
+/// User-facing text of JSX which is not translated.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardcodedString {
+    pub text: String,
+    /// The attribute the text is the value of, or `None` for text of children.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<String>,
+    pub loc: SourceLocation,
+}
+
+/// Whether the text is user-facing, i.e. contains letters. Punctuation, numbers and
+/// symbols do not need to be translated.
+pub fn is_user_facing(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
+/// The text of a string literal, or of a template literal without expressions.
+pub fn get_static_text(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Lit(Lit::Str(str)) => Some(&str.value),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+            tpl.quasis.first().and_then(|quasi| quasi.cooked.as_deref())
+        }
+        Expr::Paren(paren) => get_static_text(&paren.expr),
+        _ => None,
+    }
+}
+
+/// The name of the element as written, i.e. `Trans.Raw` or `svg:text`.
+pub fn get_element_name(name: &JSXElementName) -> String {
+    match name {
+        JSXElementName::Ident(ident) => ident.sym.to_string(),
+        JSXElementName::JSXMemberExpr(member) => get_member_name(member),
+        JSXElementName::JSXNamespacedName(name) => get_namespaced_name(name),
+    }
+}
+
+/// The name of the attribute as written, i.e. `aria-label` or `xlink:title`.
+pub fn get_attr_name(name: &JSXAttrName) -> String {
+    match name {
+        JSXAttrName::Ident(ident) => ident.sym.to_string(),
+        JSXAttrName::JSXNamespacedName(name) => get_namespaced_name(name),
+    }
+}
+
+fn get_member_name(member: &JSXMemberExpr) -> String {
+    let obj = match &member.obj {
+        JSXObject::Ident(ident) => ident.sym.to_string(),
+        JSXObject::JSXMemberExpr(member) => get_member_name(member),
+    };
+    format!("{}.{}", obj, member.prop.sym)
+}
+
+fn get_namespaced_name(name: &JSXNamespacedName) -> String {
+    format!("{}:{}", name.ns.sym, name.name.sym)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::{
+        common::DUMMY_SP,
+        ecma::ast::{Ident, Str, Tpl, TplElement},
+    };
+
+    use super::*;
+
+    #[test]
+    fn user_facing_text() {
+        assert!(is_user_facing("Hello"));
+        assert!(is_user_facing("Größe: 42"));
+        assert!(is_user_facing("こんにちは"));
+        assert!(!is_user_facing(" 42 "));
+        assert!(!is_user_facing("— / · ©"));
+    }
+
+    #[test]
+    fn static_text() {
+        let str = Expr::Lit(Lit::Str(Str::from("Hello")));
+        let tpl = |exprs: Vec<Box<Expr>>| {
+            Expr::Tpl(Tpl {
+                span: DUMMY_SP,
+                exprs,
+                quasis: vec![TplElement {
+                    span: DUMMY_SP,
+                    tail: true,
+                    cooked: Some("Hello".into()),
+                    raw: "Hello".into(),
+                }],
+            })
+        };
+        let ident = Expr::Ident(Ident::new("name".into(), DUMMY_SP));
+
+        assert_eq!(get_static_text(&str), Some("Hello"));
+        assert_eq!(get_static_text(&tpl(vec![])), Some("Hello"));
+        assert_eq!(get_static_text(&tpl(vec![Box::new(ident.clone())])), None);
+        assert_eq!(get_static_text(&ident), None);
+    }
+
+    #[test]
+    fn element_names() {
+        let ident = |name: &str| Ident::new(name.into(), DUMMY_SP);
+
+        assert_eq!(
+            get_element_name(&JSXElementName::JSXMemberExpr(JSXMemberExpr {
+                obj: JSXObject::JSXMemberExpr(Box::new(JSXMemberExpr {
+                    obj: JSXObject::Ident(ident("Trans")),
+                    prop: ident("Raw"),
+                })),
+                prop: ident("Text"),
+            })),
+            "Trans.Raw.Text"
+        );
+        assert_eq!(
+            get_attr_name(&JSXAttrName::JSXNamespacedName(JSXNamespacedName {
+                ns: ident("xlink"),
+                name: ident("title"),
+            })),
+            "xlink:title"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::ecma::ast::{ImportSpecifier, ModuleDecl, ModuleExportName, ModuleItem};
+
+/// Modules whose exports are always treated as formatjs APIs.
+pub static DEFAULT_MODULE_SOURCE_NAMES: &[&str] = &["react-intl", "@formatjs/intl"];
+
+#[derive(Debug, Clone)]
+enum ImportedBinding {
+    /// `import { imported as local } from 'source'`, or `import local from 'source'` as the
+    /// `default` export.
+    Named { imported: String, is_known: bool },
+    /// `import * as local from 'source'`
+    Namespace { is_known: bool },
+}
+
+/// Resolves identifiers of a module to the formatjs APIs they are imported as.
+#[derive(Debug, Clone, Default)]
+pub struct ImportResolver {
+    imports: HashMap<String, ImportedBinding>,
+    /// Whether identifiers which are not imported at all refer to the API of the same name.
+    match_unimported: bool,
+}
+
+impl ImportResolver {
+    pub fn collect(
+        items: &[ModuleItem],
+        module_source_names: &HashSet<String>,
+        match_unimported: bool,
+    ) -> Self {
+        let mut imports: HashMap<String, ImportedBinding> = Default::default();
+
+        for item in items {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item {
+                let is_known = module_source_names.contains(&*import_decl.src.value);
+
+                for specifier in &import_decl.specifiers {
+                    let (local, binding) = match specifier {
+                        ImportSpecifier::Named(named) => {
+                            let imported = match &named.imported {
+                                Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                                Some(ModuleExportName::Str(str)) => str.value.to_string(),
+                                None => named.local.sym.to_string(),
+                            };
+                            (&named.local, ImportedBinding::Named { imported, is_known })
+                        }
+                        ImportSpecifier::Default(default) => (
+                            &default.local,
+                            ImportedBinding::Named {
+                                imported: "default".to_string(),
+                                is_known,
+                            },
+                        ),
+                        ImportSpecifier::Namespace(namespace) => {
+                            (&namespace.local, ImportedBinding::Namespace { is_known })
+                        }
+                    };
+                    imports.insert(local.sym.to_string(), binding);
+                }
+            }
+        }
+
+        ImportResolver {
+            imports,
+            match_unimported,
+        }
+    }
+
+    /// Returns the name a local identifier refers to. That is the imported name if it is
+    /// imported from one of the formatjs modules, or the identifier itself if it's not
+    /// imported at all and unimported names are matched. Returns `None` if it's imported
+    /// from any other module, or if it's a namespace import.
+    pub fn resolve_name<'a>(&'a self, local: &'a str) -> Option<&'a str> {
+        match self.imports.get(local) {
+            None if self.match_unimported => Some(local),
+            None => None,
+            Some(ImportedBinding::Named {
+                imported,
+                is_known: true,
+            }) => Some(imported),
+            Some(_) => None,
+        }
+    }
+
+    /// Returns the local name of an export of the formatjs modules, i.e. `Message` for
+    /// `import { FormattedMessage as Message } from 'react-intl'`.
+    pub fn find_local(&self, imported_name: &str) -> Option<&str> {
+        self.imports
+            .iter()
+            .filter(|(_, binding)| {
+                matches!(binding, ImportedBinding::Named { imported, is_known: true } if imported == imported_name)
+            })
+            .map(|(local, _)| local.as_str())
+            .min()
+    }
+
+    /// Returns the local name of a namespace import of the formatjs modules.
+    pub fn find_namespace(&self) -> Option<&str> {
+        self.imports
+            .iter()
+            .filter(|(_, binding)| matches!(binding, ImportedBinding::Namespace { is_known: true }))
+            .map(|(local, _)| local.as_str())
+            .min()
+    }
+
+    /// Whether the identifier is imported from any module.
+    pub fn is_imported(&self, local: &str) -> bool {
+        self.imports.contains_key(local)
+    }
+
+    /// Register an import of a formatjs API added by the transform.
+    pub fn declare(&mut self, local: &str, imported: &str) {
+        self.imports.insert(
+            local.to_string(),
+            ImportedBinding::Named {
+                imported: imported.to_string(),
+                is_known: true,
+            },
+        );
+    }
+
+    /// Whether a local identifier is a namespace import of one of the formatjs modules,
+    /// i.e. `ReactIntl` in `import * as ReactIntl from 'react-intl'`.
+    pub fn is_namespace(&self, local: &str) -> bool {
+        matches!(
+            self.imports.get(local),
+            Some(ImportedBinding::Namespace { is_known: true })
+        )
+    }
+}
@@ -0,0 +1,327 @@
+use std::hash::Hasher;
+
+use base64ct::{Base64, Base64UrlUnpadded, Encoding};
+use md4::Md4;
+use md5::Md5;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex as Regexp};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use twox_hash::XxHash64;
+
+static HASH_REGEX: Lazy<Regexp> = Lazy::new(|| {
+    Regexp::new(r"(?i)\[(?:([^:\]]+):)?(?:hash|contenthash)(?::([a-z]+\d*))?(?::(\d+))?\]").unwrap()
+});
+static EXT_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"(?i)\[ext\]").unwrap());
+static NAME_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"(?i)\[name\]").unwrap());
+static PATH_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"(?i)\[path\]").unwrap());
+static FOLDER_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"(?i)\[folder\]").unwrap());
+static QUERY_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"(?i)\[query\]").unwrap());
+static PARENT_DIR_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"\.\.(/)?").unwrap());
+
+fn get_base_encode_table(base: &str) -> Option<&'static [u8]> {
+    let table: &'static [u8] = match base {
+        "26" => b"abcdefghijklmnopqrstuvwxyz",
+        "36" => b"0123456789abcdefghijklmnopqrstuvwxyz",
+        "49" => b"abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+        "52" => b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        "58" => b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+        "62" => b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        _ => return None,
+    };
+    Some(table)
+}
+
+/// Encode the digest as a number in the given base, reading the bytes as a little endian
+/// integer the same way loader-utils does.
+fn encode_buffer_to_base(buffer: &[u8], table: &[u8]) -> String {
+    let base = table.len() as u32;
+    // Big endian digits of the number, in base 256.
+    let mut number: Vec<u32> = buffer.iter().rev().map(|byte| *byte as u32).collect();
+    let mut output = vec![];
+
+    while number.iter().any(|digit| *digit != 0) {
+        let mut remainder = 0;
+        for digit in number.iter_mut() {
+            let value = remainder * 256 + *digit;
+            *digit = value / base;
+            remainder = value % base;
+        }
+        output.push(table[remainder as usize]);
+    }
+
+    output.reverse();
+    String::from_utf8(output).expect("Encode table should be ascii")
+}
+
+fn digest(hash_type: &str, content: &str) -> Result<Vec<u8>, String> {
+    let content = content.as_bytes();
+    let digest = match hash_type {
+        "md4" => Md4::digest(content).to_vec(),
+        "md5" => Md5::digest(content).to_vec(),
+        "sha1" => Sha1::digest(content).to_vec(),
+        "sha256" => Sha256::digest(content).to_vec(),
+        "sha512" => Sha512::digest(content).to_vec(),
+        "xxhash64" => {
+            let mut hasher = XxHash64::with_seed(0);
+            hasher.write(content);
+            hasher.finish().to_be_bytes().to_vec()
+        }
+        _ => return Err(format!("Unsupported hash type: {}", hash_type)),
+    };
+    Ok(digest)
+}
+
+/// Equivalent of loader-utils' `getHashDigest`.
+pub fn get_hash_digest(
+    content: &str,
+    hash_type: Option<&str>,
+    digest_type: Option<&str>,
+    max_length: Option<usize>,
+) -> Result<String, String> {
+    let hash_type = hash_type.unwrap_or("md5").to_lowercase();
+    let digest_type = digest_type.unwrap_or("hex").to_lowercase();
+    let digest = digest(&hash_type, content)?;
+
+    let encoded = match digest_type.as_str() {
+        "hex" => digest.iter().map(|byte| format!("{:02x}", byte)).collect(),
+        "base64" => Base64::encode_string(&digest),
+        "base64url" => Base64UrlUnpadded::encode_string(&digest),
+        _ => match digest_type
+            .strip_prefix("base")
+            .and_then(get_base_encode_table)
+        {
+            Some(table) => encode_buffer_to_base(&digest, table),
+            None => return Err(format!("Unsupported digest type: {}", digest_type)),
+        },
+    };
+
+    Ok(match max_length {
+        Some(max_length) => encoded.chars().take(max_length).collect(),
+        None => encoded,
+    })
+}
+
+/// Resolve `.` and `..` segments of a `/` separated path.
+fn normalize_segments(path: &str) -> Vec<&str> {
+    let mut segments: Vec<&str> = vec![];
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                if matches!(segments.last(), Some(last) if *last != "..") {
+                    segments.pop();
+                } else if !path.starts_with('/') {
+                    segments.push(segment);
+                }
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments
+}
+
+/// Lexical equivalent of node's `path.relative`.
+fn relative_path(from: &str, to: &str) -> String {
+    let from = normalize_segments(from);
+    let to = normalize_segments(to);
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(from, to)| from == to)
+        .count();
+
+    let mut segments = vec![".."; from.len() - common];
+    segments.extend(&to[common..]);
+    segments.join("/")
+}
+
+/// Splits a path into its directory, file name without extension and extension,
+/// the same way node's `path.parse` does.
+fn parse_path(path: &str) -> (&str, &str, &str) {
+    let (dir, base) = match path.rfind('/') {
+        Some(0) => ("/", &path[1..]),
+        Some(idx) => (&path[..idx], &path[idx + 1..]),
+        None => ("", path),
+    };
+    match base.rfind('.') {
+        Some(idx) if idx > 0 => (dir, &base[..idx], &base[idx + 1..]),
+        _ => (dir, base, ""),
+    }
+}
+
+/// Port of loader-utils' `interpolateName`, used to generate message ids. `[ext]`, `[name]`,
+/// `[path]`, `[folder]` and `[query]` are derived from the resource path relative to the
+/// `context` directory if given, and `[<hashType>:contenthash:<digestType>:<length>]` is
+/// replaced with the digest of the content.
+pub fn interpolate_name(
+    resource_path: &str,
+    name: &str,
+    content: &str,
+    context: Option<&str>,
+) -> Result<String, String> {
+    let filename = if name.is_empty() {
+        "[hash].[ext]"
+    } else {
+        name
+    };
+
+    let mut ext = "bin";
+    let mut basename = "file";
+    let mut directory = String::new();
+    let mut folder = "";
+    let mut query = "";
+
+    let (resource_path, resource_query) = match resource_path.find('?') {
+        Some(idx) => (&resource_path[..idx], &resource_path[idx..]),
+        None => (resource_path, ""),
+    };
+    let resource_path = resource_path.replace('\\', "/");
+
+    if !resource_path.is_empty() {
+        let (dir, name, extension) = parse_path(&resource_path);
+        let mut resource_path = resource_path.clone();
+
+        if !extension.is_empty() {
+            ext = extension;
+        }
+
+        if !dir.is_empty() {
+            basename = name;
+            resource_path = format!("{}/", dir.trim_end_matches('/'));
+        }
+
+        directory = if let Some(context) = context {
+            let context = context.replace('\\', "/");
+            let mut directory = relative_path(&context, &format!("{}_", resource_path));
+            directory = PARENT_DIR_REGEX.replace_all(&directory, "_$1").to_string();
+            directory.pop();
+            directory
+        } else {
+            PARENT_DIR_REGEX
+                .replace_all(&resource_path, "_$1")
+                .to_string()
+        };
+
+        if directory.len() == 1 {
+            directory = String::new();
+        } else if directory.len() > 1 {
+            folder = directory
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or_default();
+        }
+    }
+
+    if resource_query.len() > 1 {
+        query = resource_query.split('#').next().unwrap_or_default();
+    }
+
+    let mut url = filename.to_string();
+    if !content.is_empty() {
+        let mut error = None;
+        url = HASH_REGEX
+            .replace_all(&url, |cap: &Captures| {
+                let hash_type = cap.get(1).map(|m| m.as_str());
+                let digest_type = cap.get(2).map(|m| m.as_str());
+                let max_length = cap.get(3).and_then(|m| m.as_str().parse::<usize>().ok());
+
+                get_hash_digest(content, hash_type, digest_type, max_length).unwrap_or_else(|e| {
+                    error = Some(e);
+                    String::new()
+                })
+            })
+            .to_string();
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+    }
+
+    url = EXT_REGEX.replace_all(&url, ext).to_string();
+    url = NAME_REGEX.replace_all(&url, basename).to_string();
+    url = PATH_REGEX.replace_all(&url, directory.as_str()).to_string();
+    url = FOLDER_REGEX.replace_all(&url, folder).to_string();
+    url = QUERY_REGEX.replace_all(&url, query).to_string();
+
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn hash_digest() {
+        let digest = |hash_type, digest_type, max_length| {
+            get_hash_digest("hello", Some(hash_type), Some(digest_type), max_length).unwrap()
+        };
+
+        assert_eq!(
+            digest("md5", "hex", None),
+            "5d41402abc4b2a76b9719d911017c592"
+        );
+        assert_eq!(digest("md4", "hex", Some(8)), "866437cb");
+        assert_eq!(digest("sha1", "hex", Some(8)), "aaf4c61d");
+        assert_eq!(digest("sha256", "base64", Some(8)), "LPJNul+w");
+        assert_eq!(digest("sha512", "base64url", Some(8)), "m3HSJL1i");
+        assert_eq!(digest("xxhash64", "hex", None), "26c7827d889f6da3");
+        assert_eq!(
+            get_hash_digest("hello", None, None, None).unwrap(),
+            digest("md5", "hex", None)
+        );
+        assert!(get_hash_digest("hello", Some("sha3"), None, None).is_err());
+        assert!(get_hash_digest("hello", None, Some("base7"), None).is_err());
+    }
+
+    #[test]
+    fn base_encoded_digest() {
+        assert_eq!(encode_buffer_to_base(&[0x01, 0x01], b"0123456789"), "257");
+        assert_eq!(encode_buffer_to_base(&[0x00, 0x00], b"0123456789"), "");
+    }
+
+    #[test]
+    fn file_placeholders() {
+        let interpolate = |resource_path, context| {
+            interpolate_name(
+                resource_path,
+                "[path]|[folder]|[name]|[ext]|[query]",
+                "hello",
+                context,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            interpolate("/app/src/components/Foo.tsx", None),
+            "/app/src/components/|components|Foo|tsx|"
+        );
+        assert_eq!(
+            interpolate("/app/src/components/Foo.tsx", Some("/app")),
+            "src/components/|components|Foo|tsx|"
+        );
+        assert_eq!(
+            interpolate("/app/src/Foo.tsx?raw#bar", Some("/app/lib")),
+            "_/src/|src|Foo|tsx|?raw"
+        );
+        assert_eq!(interpolate("/app/Foo", Some("/app")), "||Foo|bin|");
+        assert_eq!(interpolate("", None), "||file|bin|");
+    }
+
+    #[test]
+    fn content_hash() {
+        assert_eq!(
+            interpolate_name(
+                "/app/Foo.tsx",
+                "[name]_[md5:contenthash:hex:8]_[hash:base36:4]",
+                "hello",
+                None,
+            )
+            .unwrap(),
+            "Foo_5d41402a_8ot4"
+        );
+    }
+}
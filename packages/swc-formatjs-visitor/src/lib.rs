@@ -1,42 +1,435 @@
-use std::collections::{HashMap, HashSet};
-
-use base64ct::{Base64, Encoding};
-use icu_messageformat_parser::{Parser, ParserOptions};
+mod aliases;
+mod children;
+pub mod consistency;
+mod directives;
+pub mod duplicates;
+mod entities;
+mod evaluate;
+mod hardcoded;
+mod imports;
+mod interpolate;
+mod literal;
+mod message_span;
+mod pragma;
+mod references;
+mod template;
+mod translations;
+mod values;
+mod whitespace;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use aliases::{collect_pat_bindings, get_static_prop_name, FormatMessageScopes};
+use children::{clean_jsx_text, ChildrenMessage};
+use directives::{collect_comments, comment_description, FileDirectives, OVERRIDABLE_OPTIONS};
+use entities::{create_jsx_str_attr_value, get_jsx_str_value, get_jsx_text_value};
+use evaluate::{stable_stringify, ConstBindings};
+pub use hardcoded::HardcodedString;
+use hardcoded::{
+    get_attr_name, get_element_name, get_static_text, is_user_facing, DEFAULT_CHECKED_ATTRIBUTES,
+};
+use icu_messageformat_parser::{Error as ParserError, Parser, ParserOptions};
+use imports::{ImportResolver, DEFAULT_MODULE_SOURCE_NAMES};
+use interpolate::get_hash_digest;
+use literal::json_value_to_expr;
+use message_span::MessageSourceMap;
 use once_cell::sync::Lazy;
-use regex::{Captures, Regex as Regexp};
-use serde::{ser::SerializeMap, Deserialize, Serialize};
-use sha2::{Digest, Sha512};
+use pragma::parse_pragma;
+use references::{DescriptorOrigin, DescriptorResolver, ResolvedDescriptor};
+use regex::Regex as Regexp;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+#[cfg(feature = "custom_transform")]
+use swc_core::common::errors::HANDLER;
+#[cfg(all(feature = "plugin", not(feature = "custom_transform")))]
+use swc_core::plugin::errors::HANDLER;
 use swc_core::{
     common::{
         comments::{Comment, CommentKind, Comments},
+        errors::{DiagnosticBuilder, Handler},
         source_map::Pos,
-        BytePos, Loc, SourceMapper, Span, Spanned, DUMMY_SP,
+        Loc, SourceMapper, Span, Spanned, DUMMY_SP,
     },
     ecma::{
         ast::{
-            CallExpr, Callee, Expr, ExprOrSpread, Ident, JSXAttr, JSXAttrName, JSXAttrOrSpread,
-            JSXAttrValue, JSXElementName, JSXExpr, JSXNamespacedName, JSXOpeningElement,
-            KeyValueProp, Lit, MemberProp, ModuleItem, ObjectLit, Prop, PropName, PropOrSpread,
-            Str,
+            ArrowExpr, BlockStmt, CallExpr, Callee, CatchClause, Constructor, Decl, Expr,
+            ExprOrSpread, ExprStmt, FnDecl, Function, Ident, ImportDecl, ImportNamedSpecifier,
+            ImportSpecifier, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXElement,
+            JSXElementChild, JSXElementName, JSXExpr, JSXExprContainer, JSXMemberExpr,
+            JSXNamespacedName, JSXObject, JSXOpeningElement, KeyValueProp, Lit, MemberExpr,
+            MemberProp, ModuleDecl, ModuleExportName, ModuleItem, ObjectLit, OptChainBase,
+            OptChainExpr, ParamOrTsParamProp, Pat, Prop, PropName, PropOrSpread, Stmt, Str,
+            TaggedTpl, VarDecl,
         },
         visit::{noop_visit_mut_type, VisitMut, VisitMutWith},
     },
 };
+use template::{create_callee, TemplateMessage, DEFAULT_FORMAT_MESSAGE};
+use translations::Translations;
+use values::check_values;
+use whitespace::normalize_whitespace;
+
+static DEFAULT_ID_INTERPOLATE_PATTERN: &str = "[sha512:contenthash:base64:6]";
 
 pub static WHITESPACE_REGEX: Lazy<Regexp> = Lazy::new(|| Regexp::new(r"\s+").unwrap());
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase", default)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
 pub struct FormatJSPluginOptions {
     pub pragma: String,
     pub remove_default_message: bool,
+    #[serde(alias = "idInterpolationPattern")]
     pub id_interpolate_pattern: Option<String>,
+    pub id_interpolate_root: Option<String>,
     pub ast: bool,
     pub extract_source_location: bool,
     pub preserve_whitespace: bool,
     pub __debug_extracted_messages_comment: bool,
     pub additional_function_names: Vec<String>,
     pub additional_component_names: Vec<String>,
+    pub additional_module_source_names: Vec<String>,
+    /// Match `FormattedMessage`, `defineMessages` and `formatMessage` by name when they're
+    /// not imported at all, like babel-plugin-formatjs does, i.e. for globals.
+    pub match_unimported_names: bool,
+    pub id_strategy: Option<IdStrategy>,
+    pub translations: Option<TranslationsOptions>,
+    pub id_only: Option<IdOnlyOptions>,
+    /// Options of the ICU message parser, used to validate messages and for `ast`.
+    pub parser_options: ParserOptions,
+    pub on_error: OnErrorOptions,
+    /// Markers of translator comments used as descriptions, i.e. `i18n:` for
+    /// `// i18n: Shown on the checkout button` right before a descriptor.
+    pub description_comment_markers: Vec<String>,
+    pub descriptor_keys: DescriptorKeysOptions,
+    /// Components whose JSX children are the message, i.e. `T` for
+    /// `<T>Hello <b>{name}</b></T>`. They are rewritten to `FormattedMessage`.
+    pub children_message_components: Vec<String>,
+    pub tagged_templates: TaggedTemplateOptions,
+    pub hardcoded_strings: Option<HardcodedStringsOptions>,
+    pub whitespace: WhitespaceOptions,
+    /// Rich text tags provided by react-intl's `defaultRichTextElements`. Tags without a
+    /// value are only reported as `values` problems when this is set, unless they're listed.
+    pub default_rich_text_elements: Option<Vec<String>>,
+}
+
+/// How whitespace at the edges of tags and plural / select branches is normalized, unless
+/// `preserveWhitespace` is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct WhitespaceOptions {
+    /// Whitespace right inside tags, i.e. `<b> {name} </b>`.
+    pub tag_edges: EdgeWhitespace,
+    /// Whitespace right inside branches, i.e. `one { # item }`.
+    pub branch_edges: EdgeWhitespace,
+}
+
+/// Normalization of whitespace at the edges of tags and branches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EdgeWhitespace {
+    /// Collapse into a single space, like whitespace anywhere else.
+    #[default]
+    Collapse,
+    /// Remove it entirely.
+    Trim,
+}
+
+/// Report user-facing strings of JSX which bypassed i18n, like eslint's `no-literal-string`.
+/// Text of children and of the checked attributes containing letters is reported, unless
+/// it's inside a message component. The strings of each file are listed next to the
+/// extracted messages as `hardcodedStrings`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct HardcodedStringsOptions {
+    /// Attributes checked in addition to `title`, `alt`, `placeholder` and `aria-label`.
+    pub attributes: Vec<String>,
+    /// Components whose children and attributes are not checked, i.e. `code` or `Icon`.
+    pub ignore_components: Vec<String>,
+    /// Attributes which are not checked, including the default ones.
+    pub ignore_attributes: Vec<String>,
+    /// Regular expressions of text which is not reported, i.e. `^[A-Z_]+$`.
+    pub ignore_patterns: Vec<String>,
+    /// How each hardcoded string is reported as a diagnostic, `warn` by default.
+    pub severity: DiagnosticSeverity,
+}
+
+impl Default for HardcodedStringsOptions {
+    fn default() -> Self {
+        HardcodedStringsOptions {
+            attributes: vec![],
+            ignore_components: vec![],
+            ignore_attributes: vec![],
+            ignore_patterns: vec![],
+            severity: DiagnosticSeverity::Warn,
+        }
+    }
+}
+
+/// Messages defined with tagged templates, i.e. `` t`You have ${count} items` ``, which are
+/// rewritten to `intl.formatMessage({id, defaultMessage}, {count})`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct TaggedTemplateOptions {
+    /// Tags of message templates.
+    pub tags: Vec<String>,
+    /// The function called with the descriptor and values instead, `intl.formatMessage`
+    /// by default.
+    pub format_message: Option<String>,
+}
+
+/// Role of a descriptor key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DescriptorKeyRole {
+    Id,
+    DefaultMessage,
+    Description,
+}
+
+impl DescriptorKeyRole {
+    fn as_key(self) -> &'static str {
+        match self {
+            DescriptorKeyRole::Id => "id",
+            DescriptorKeyRole::DefaultMessage => "defaultMessage",
+            DescriptorKeyRole::Description => "description",
+        }
+    }
+}
+
+/// Keys of message descriptors other than `id`, `defaultMessage` and `description`, for
+/// wrappers of the formatjs APIs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct DescriptorKeysOptions {
+    /// Alternate keys of descriptor roles, i.e. `{"message": "defaultMessage"}`.
+    pub aliases: HashMap<String, DescriptorKeyRole>,
+    /// Additional fields extracted with the message, i.e. `meaning` or `maxLength`.
+    /// Fields of extracted messages themselves, like `id` or `meta`, are rejected.
+    #[serde(deserialize_with = "deserialize_extra_fields")]
+    pub extra_fields: Vec<String>,
+    /// Remove extracted extra fields from the output, like `description`.
+    pub remove_extra_fields: bool,
+}
+
+/// Fields of `ExtractedMessage` which extra fields are flattened next to.
+static RESERVED_EXTRA_FIELDS: &[&str] = &[
+    "id",
+    "defaultMessage",
+    "description",
+    "loc",
+    "shortId",
+    "meta",
+];
+
+fn deserialize_extra_fields<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    let extra_fields = Vec::<String>::deserialize(deserializer)?;
+    if let Some(field) = extra_fields
+        .iter()
+        .find(|field| RESERVED_EXTRA_FIELDS.contains(&field.as_str()))
+    {
+        return Err(D::Error::custom(format!(
+            "`{}` is a field of extracted messages and cannot be an extra field",
+            field
+        )));
+    }
+    Ok(extra_fields)
+}
+
+impl DescriptorKeysOptions {
+    /// Returns the role of an alias as its default key, or the key itself.
+    fn resolve<'a>(&self, key: &'a str) -> &'a str {
+        match self.aliases.get(key) {
+            Some(role) => role.as_key(),
+            None => key,
+        }
+    }
+
+    fn is_extra_field(&self, key: &str) -> bool {
+        self.extra_fields.iter().any(|field| field == key)
+    }
+}
+
+/// How diagnostics of a category are reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticSeverity {
+    /// Report an error, failing the transform.
+    #[default]
+    Error,
+    /// Report a warning.
+    Warn,
+    /// Do not report anything.
+    Ignore,
+}
+
+impl DiagnosticSeverity {
+    /// Create the diagnostic with this severity, or `None` if it is ignored.
+    pub fn struct_diagnostic<'a>(
+        self,
+        handler: &'a Handler,
+        span: Option<Span>,
+        message: &str,
+    ) -> Option<DiagnosticBuilder<'a>> {
+        match (self, span) {
+            (DiagnosticSeverity::Error, Some(span)) => Some(handler.struct_span_err(span, message)),
+            (DiagnosticSeverity::Error, None) => Some(handler.struct_err(message)),
+            (DiagnosticSeverity::Warn, Some(span)) => Some(handler.struct_span_warn(span, message)),
+            (DiagnosticSeverity::Warn, None) => Some(handler.struct_warn(message)),
+            (DiagnosticSeverity::Ignore, _) => None,
+        }
+    }
+}
+
+/// Report a diagnostic with the given severity, at `span` if any.
+fn emit_diagnostic(severity: DiagnosticSeverity, span: Option<Span>, message: &str) {
+    emit_diagnostic_with(severity, span, message, |_| {});
+}
+
+/// Report a diagnostic with the given severity, letting `decorate` add notes to it.
+#[cfg_attr(
+    not(any(feature = "plugin", feature = "custom_transform")),
+    allow(unused_variables)
+)]
+fn emit_diagnostic_with(
+    severity: DiagnosticSeverity,
+    span: Option<Span>,
+    message: &str,
+    decorate: impl FnOnce(&mut DiagnosticBuilder),
+) {
+    #[cfg(any(feature = "plugin", feature = "custom_transform"))]
+    HANDLER.with(|handler| {
+        if let Some(mut diagnostic) = severity.struct_diagnostic(handler, span, message) {
+            decorate(&mut diagnostic);
+            diagnostic.emit()
+        }
+    });
+}
+
+/// Severity of each category of diagnostics, i.e. to only warn in development builds
+/// while failing CI builds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct OnErrorOptions {
+    /// Messages which are not valid ICU messages.
+    pub parse: DiagnosticSeverity,
+    /// Descriptor values which cannot be statically evaluated.
+    pub evaluation: DiagnosticSeverity,
+    /// Malformed message descriptors, like descriptors without an `id` or `defaultMessage`
+    /// and `defineMessages` called without an object.
+    pub descriptor: DiagnosticSeverity,
+    /// Ids which cannot be generated, do not match `idStrategy.validate` or collide as
+    /// short ids.
+    pub id: DiagnosticSeverity,
+    /// Messages declared with the same id but different content.
+    pub duplicate: DiagnosticSeverity,
+    /// Malformed comment directives, like `@formatjs` overrides of unknown options or
+    /// pragma entries which are not `key:value`.
+    pub directive: DiagnosticSeverity,
+    /// `values` of `formatMessage` calls and `FormattedMessage` elements which do not match
+    /// the placeholders of the message. Not checked by default.
+    pub values: DiagnosticSeverity,
+    /// Plugin options which cannot be used, like catalog files which cannot be read or
+    /// regular expressions which do not compile. Reported once per visitor.
+    pub config: DiagnosticSeverity,
+}
+
+impl Default for OnErrorOptions {
+    fn default() -> Self {
+        OnErrorOptions {
+            parse: DiagnosticSeverity::Error,
+            evaluation: DiagnosticSeverity::Error,
+            descriptor: DiagnosticSeverity::Error,
+            id: DiagnosticSeverity::Error,
+            duplicate: DiagnosticSeverity::Error,
+            directive: DiagnosticSeverity::Error,
+            values: DiagnosticSeverity::Ignore,
+            config: DiagnosticSeverity::Error,
+        }
+    }
+}
+
+/// Reduce message descriptors to their ids, so that messages do not end up in bundles.
+/// `defineMessages` / `defineMessage` calls are replaced with their argument.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct IdOnlyOptions {
+    /// Replace descriptors of `defineMessages` / `defineMessage` with the bare id string
+    /// instead of `{id}`.
+    pub bare_id: bool,
+    /// Replace ids with short ids of the given length, derived from a hash of the id.
+    /// Extracted messages list the short id of each message as `shortId`.
+    pub short_id_length: Option<usize>,
+}
+
+/// Declarative replacement of babel-plugin-formatjs' `overrideIdFn`, which cannot be
+/// passed to the wasm plugin.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct IdStrategy {
+    /// Pattern of generated ids. In addition to the placeholders of `idInterpolatePattern`,
+    /// `[key]` (the key of the message in `defineMessages`), `[id]` and `[description]`
+    /// are replaced. The key and the id are hashed with the message when they're used, while
+    /// the description is inserted with other characters than letters, digits, `_` and `-`
+    /// replaced by `_`.
+    pub template: Option<String>,
+    /// Prefix ids with the directory of the file relative to `idInterpolateRoot`,
+    /// i.e. `components.greeting` for `greeting` in `components/Greeting.tsx`. Relative
+    /// filenames are used as is without `idInterpolateRoot`.
+    pub prefix_by_directory: bool,
+    /// Generate ids for messages which already have one as well.
+    pub override_existing: bool,
+    /// Regular expression ids written in the source have to match.
+    pub validate: Option<String>,
+}
+
+/// Inline translations of a locale in place of `defaultMessage`, for builds per locale.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct TranslationsOptions {
+    pub locale: String,
+    /// Locales to look up missing translations from, after the parent locales of `locale`.
+    pub fallback_locales: Vec<String>,
+    /// Catalogs embedded into the options, by locale.
+    pub catalogs: HashMap<String, HashMap<String, CatalogMessage>>,
+    /// Paths of catalog JSON files, by locale.
+    pub catalog_files: HashMap<String, String>,
+    /// How messages without translation are reported, `warn` by default. Their
+    /// `defaultMessage` is inlined instead.
+    pub on_missing: DiagnosticSeverity,
+}
+
+impl Default for TranslationsOptions {
+    fn default() -> Self {
+        TranslationsOptions {
+            locale: Default::default(),
+            fallback_locales: vec![],
+            catalogs: Default::default(),
+            catalog_files: Default::default(),
+            on_missing: DiagnosticSeverity::Warn,
+        }
+    }
+}
+
+/// A message of a catalog, either compiled as `{"id": "message"}` or extracted as
+/// `{"id": {"defaultMessage": "message"}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CatalogMessage {
+    Str(String),
+    #[serde(rename_all = "camelCase")]
+    Descriptor {
+        default_message: String,
+    },
+}
+
+impl CatalogMessage {
+    pub fn into_message(self) -> String {
+        match self {
+            CatalogMessage::Str(message) => message,
+            CatalogMessage::Descriptor { default_message } => default_message,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -44,6 +437,7 @@ pub struct JSXMessageDescriptorPath {
     id: Option<JSXAttrValue>,
     default_message: Option<JSXAttrValue>,
     description: Option<JSXAttrValue>,
+    extra: Vec<(String, Option<JSXAttrValue>)>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -51,6 +445,7 @@ pub struct CallExprMessageDescriptorPath {
     id: Option<Expr>,
     default_message: Option<Expr>,
     description: Option<Expr>,
+    extra: Vec<(String, Expr)>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -58,22 +453,31 @@ pub struct MessageDescriptor {
     id: Option<String>,
     default_message: Option<String>,
     description: Option<MessageDescriptionValue>,
+    generated_id: bool,
+    short_id: Option<String>,
+    extra: BTreeMap<String, serde_json::Value>,
 }
 
 // TODO: consolidate with get_message_descriptor_key_from_call_expr?
-fn get_message_descriptor_key_from_jsx(name: &JSXAttrName) -> &str {
+fn get_message_descriptor_key_from_jsx<'a>(
+    name: &'a JSXAttrName,
+    keys: &DescriptorKeysOptions,
+) -> &'a str {
     match name {
         JSXAttrName::Ident(name)
-        | JSXAttrName::JSXNamespacedName(JSXNamespacedName { name, .. }) => &*name.sym,
+        | JSXAttrName::JSXNamespacedName(JSXNamespacedName { name, .. }) => keys.resolve(&name.sym),
     }
 
     // NOTE: Do not support evaluatePath()
 }
 
-fn get_message_descriptor_key_from_call_expr(name: &PropName) -> Option<&str> {
+fn get_message_descriptor_key_from_call_expr<'a>(
+    name: &'a PropName,
+    keys: &DescriptorKeysOptions,
+) -> Option<&'a str> {
     match name {
-        PropName::Ident(name) => Some(&*name.sym),
-        PropName::Str(name) => Some(&*name.value),
+        PropName::Ident(name) => Some(keys.resolve(&name.sym)),
+        PropName::Str(name) => Some(keys.resolve(&name.value)),
         _ => None,
     }
 
@@ -83,11 +487,12 @@ fn get_message_descriptor_key_from_call_expr(name: &PropName) -> Option<&str> {
 // TODO: Consolidate with create_message_descriptor_from_call_expr
 fn create_message_descriptor_from_jsx_attr(
     attrs: &Vec<JSXAttrOrSpread>,
+    keys: &DescriptorKeysOptions,
 ) -> JSXMessageDescriptorPath {
     let mut ret = JSXMessageDescriptorPath::default();
     for attr in attrs {
         if let JSXAttrOrSpread::JSXAttr(JSXAttr { name, value, .. }) = attr {
-            let key = get_message_descriptor_key_from_jsx(name);
+            let key = get_message_descriptor_key_from_jsx(name, keys);
 
             match key {
                 "id" => {
@@ -99,6 +504,9 @@ fn create_message_descriptor_from_jsx_attr(
                 "description" => {
                     ret.description = value.clone();
                 }
+                key if keys.is_extra_field(key) => {
+                    ret.extra.push((key.to_string(), value.clone()));
+                }
                 _ => {
                     //unexpected
                 }
@@ -111,12 +519,13 @@ fn create_message_descriptor_from_jsx_attr(
 
 fn create_message_descriptor_from_call_expr(
     props: &Vec<PropOrSpread>,
+    keys: &DescriptorKeysOptions,
 ) -> CallExprMessageDescriptorPath {
     let mut ret = CallExprMessageDescriptorPath::default();
     for prop in props {
         if let PropOrSpread::Prop(prop) = prop {
             if let Prop::KeyValue(KeyValueProp { key, value }) = &**prop {
-                if let Some(key) = get_message_descriptor_key_from_call_expr(key) {
+                if let Some(key) = get_message_descriptor_key_from_call_expr(key, keys) {
                     match key {
                         "id" => {
                             ret.id = Some(*value.clone());
@@ -127,6 +536,9 @@ fn create_message_descriptor_from_call_expr(
                         "description" => {
                             ret.description = Some(*value.clone());
                         }
+                        key if keys.is_extra_field(key) => {
+                            ret.extra.push((key.to_string(), *value.clone()));
+                        }
                         _ => {
                             //unexpected
                         }
@@ -139,16 +551,74 @@ fn create_message_descriptor_from_call_expr(
     ret
 }
 
+/// Statically evaluate a message descriptor value, reporting it with the given severity
+/// if it cannot be evaluated instead of extracting a wrong message.
+fn evaluate_message_descriptor_value(
+    expr: &Expr,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
+) -> Option<String> {
+    let value = bindings.evaluate_string(expr);
+
+    if value.is_none() {
+        emit_diagnostic(
+            severity,
+            Some(expr.span()),
+            "[React Intl] Messages must be statically evaluate-able for extraction.",
+        );
+    }
+
+    value
+}
+
+/// Statically evaluate a description, which may be any JSON value such as
+/// `{context: "...", maxLength: 40}`. Reports the part which cannot be evaluated with the
+/// given severity.
+fn evaluate_message_description(
+    expr: &Expr,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
+) -> Option<MessageDescriptionValue> {
+    match evaluate_json_descriptor_value(
+        expr,
+        bindings,
+        severity,
+        "[React Intl] Descriptions must be statically evaluate-able for extraction.",
+    )? {
+        serde_json::Value::String(description) => Some(MessageDescriptionValue::Str(description)),
+        description => Some(MessageDescriptionValue::Obj(description)),
+    }
+}
+
+/// Statically evaluate a descriptor value to JSON, reporting the part which cannot be
+/// evaluated with the given severity and message.
+fn evaluate_json_descriptor_value(
+    expr: &Expr,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
+    message: &str,
+) -> Option<serde_json::Value> {
+    match bindings.evaluate_json(expr) {
+        Ok(value) => Some(value),
+        Err(span) => {
+            emit_diagnostic(severity, Some(span), message);
+
+            None
+        }
+    }
+}
+
 fn get_jsx_message_descriptor_value(
     value: &Option<JSXAttrValue>,
     is_message_node: Option<bool>,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
 ) -> Option<String> {
     if value.is_none() {
         return None;
     }
     let value = value.as_ref().expect("Should be available");
 
-    // NOTE: do not support evaluatePath
     match value {
         JSXAttrValue::JSXExprContainer(container) => {
             if is_message_node.unwrap_or(false) {
@@ -161,33 +631,12 @@ fn get_jsx_message_descriptor_value(
             }
 
             return match &container.expr {
-                JSXExpr::Expr(expr) => match &**expr {
-                    Expr::Lit(lit) => match &lit {
-                        Lit::Str(str) => Some(str.value.to_string()),
-                        _ => None,
-                    },
-                    Expr::Tpl(tpl) => {
-                        //NOTE: This doesn't fully evaluate templates
-                        Some(
-                            tpl.quasis
-                                .iter()
-                                .map(|q| {
-                                    q.cooked
-                                        .as_ref()
-                                        .map(|v| v.to_string())
-                                        .unwrap_or("".to_string())
-                                })
-                                .collect::<Vec<String>>()
-                                .join(""),
-                        )
-                    }
-                    _ => None,
-                },
+                JSXExpr::Expr(expr) => evaluate_message_descriptor_value(expr, bindings, severity),
                 _ => None,
             };
         }
         JSXAttrValue::Lit(lit) => match &lit {
-            Lit::Str(str) => Some(str.value.to_string()),
+            Lit::Str(str) => Some(get_jsx_str_value(str)),
             _ => None,
         },
         _ => None,
@@ -197,6 +646,8 @@ fn get_jsx_message_descriptor_value(
 fn get_call_expr_message_descriptor_value(
     value: &Option<Expr>,
     _is_message_node: Option<bool>,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
 ) -> Option<String> {
     if value.is_none() {
         return None;
@@ -204,108 +655,42 @@ fn get_call_expr_message_descriptor_value(
 
     let value = value.as_ref().expect("Should be available");
 
-    // NOTE: do not support evaluatePath
-    match value {
-        Expr::Ident(ident) => Some(ident.sym.to_string()),
-        Expr::Lit(lit) => match &lit {
-            Lit::Str(str) => Some(str.value.to_string()),
-            _ => None,
-        },
-        Expr::Tpl(tpl) => {
-            //NOTE: This doesn't fully evaluate templates
-            Some(
-                tpl.quasis
-                    .iter()
-                    .map(|q| {
-                        q.cooked
-                            .as_ref()
-                            .map(|v| v.to_string())
-                            .unwrap_or("".to_string())
-                    })
-                    .collect::<Vec<String>>()
-                    .join(""),
-            )
-        }
-        _ => None,
-    }
+    evaluate_message_descriptor_value(value, bindings, severity)
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
 pub enum MessageDescriptionValue {
     Str(String),
-    Obj(ObjectLit),
+    /// Structured description, any JSON value other than a string.
+    Obj(serde_json::Value),
 }
 
-impl Serialize for MessageDescriptionValue {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
+impl MessageDescriptionValue {
+    /// The description as hashed into generated ids: structured descriptions are
+    /// stringified with sorted keys, like babel-plugin-formatjs' `json-stable-stringify`.
+    pub fn to_content_string(&self) -> String {
         match self {
-            MessageDescriptionValue::Str(str) => serializer.serialize_str(str),
-            // NOTE: this is good enough to barely pass key-value object serialization. Not a complete implementation.
-            MessageDescriptionValue::Obj(obj) => {
-                let mut state = serializer.serialize_map(Some(obj.props.len()))?;
-                for prop in &obj.props {
-                    match prop {
-                        PropOrSpread::Prop(prop) => {
-                            match &**prop {
-                                Prop::KeyValue(key_value) => {
-                                    let key = match &key_value.key {
-                                        PropName::Ident(ident) => ident.sym.to_string(),
-                                        PropName::Str(str) => str.value.to_string(),
-                                        _ => {
-                                            //unexpected
-                                            continue;
-                                        }
-                                    };
-                                    let value = match &*key_value.value {
-                                        Expr::Lit(lit) => match &lit {
-                                            Lit::Str(str) => str.value.to_string(),
-                                            _ => {
-                                                //unexpected
-                                                continue;
-                                            }
-                                        },
-                                        _ => {
-                                            //unexpected
-                                            continue;
-                                        }
-                                    };
-                                    state.serialize_entry(&key, &value)?;
-                                }
-                                _ => {
-                                    //unexpected
-                                    continue;
-                                }
-                            }
-                        }
-                        _ => {
-                            //unexpected
-                            continue;
-                        }
-                    }
-                }
-                state.end()
-            }
+            MessageDescriptionValue::Str(description) => description.clone(),
+            MessageDescriptionValue::Obj(description) => stable_stringify(description),
         }
     }
 }
 
-// NOTE: due to not able to support static evaluation, this
-// fn manually expands possible values for the description values
+// NOTE: this fn manually expands possible values for the description values
 // from string to object.
 //TODO: Consolidate with get_call_expr_message_descriptor_value_maybe_object
 fn get_jsx_message_descriptor_value_maybe_object(
     value: &Option<JSXAttrValue>,
     is_message_node: Option<bool>,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
 ) -> Option<MessageDescriptionValue> {
     if value.is_none() {
         return None;
     }
     let value = value.as_ref().expect("Should be available");
 
-    // NOTE: do not support evaluatePath
     match value {
         JSXAttrValue::JSXExprContainer(container) => {
             if is_message_node.unwrap_or(false) {
@@ -318,21 +703,12 @@ fn get_jsx_message_descriptor_value_maybe_object(
             }
 
             return match &container.expr {
-                JSXExpr::Expr(expr) => match &**expr {
-                    Expr::Lit(lit) => match &lit {
-                        Lit::Str(str) => Some(MessageDescriptionValue::Str(str.value.to_string())),
-                        _ => None,
-                    },
-                    Expr::Object(object_lit) => {
-                        Some(MessageDescriptionValue::Obj(object_lit.clone()))
-                    }
-                    _ => None,
-                },
+                JSXExpr::Expr(expr) => evaluate_message_description(expr, bindings, severity),
                 _ => None,
             };
         }
         JSXAttrValue::Lit(lit) => match &lit {
-            Lit::Str(str) => Some(MessageDescriptionValue::Str(str.value.to_string())),
+            Lit::Str(str) => Some(MessageDescriptionValue::Str(get_jsx_str_value(str))),
             _ => None,
         },
         _ => None,
@@ -342,101 +718,73 @@ fn get_jsx_message_descriptor_value_maybe_object(
 fn get_call_expr_message_descriptor_value_maybe_object(
     value: &Option<Expr>,
     _is_message_node: Option<bool>,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
 ) -> Option<MessageDescriptionValue> {
     if value.is_none() {
         return None;
     }
 
     let value = value.as_ref().expect("Should be available");
-    // NOTE: do not support evaluatePath
-    match value {
-        Expr::Ident(ident) => Some(MessageDescriptionValue::Str(ident.sym.to_string())),
-        Expr::Lit(lit) => match &lit {
-            Lit::Str(str) => Some(MessageDescriptionValue::Str(str.value.to_string())),
-            _ => None,
-        },
-        Expr::Object(object_lit) => Some(MessageDescriptionValue::Obj(object_lit.clone())),
-        _ => None,
-    }
+    evaluate_message_description(value, bindings, severity)
 }
 
 // TODO: Consolidate with get_call_expr_icu_message_value
 fn get_jsx_icu_message_value(
     message_path: &Option<JSXAttrValue>,
     preserve_whitespace: bool,
+    whitespace_options: &WhitespaceOptions,
+    parser_options: &ParserOptions,
+    bindings: &ConstBindings,
+    on_error: &OnErrorOptions,
 ) -> String {
     if message_path.is_none() {
         return "".to_string();
     }
 
     let message =
-        get_jsx_message_descriptor_value(message_path, Some(true)).unwrap_or("".to_string());
+        get_jsx_message_descriptor_value(message_path, Some(true), bindings, on_error.evaluation)
+            .unwrap_or("".to_string());
 
     let message = if !preserve_whitespace {
-        let message = WHITESPACE_REGEX.replace_all(&message, " ");
-        message.trim().to_string()
+        normalize_whitespace(&message, parser_options, whitespace_options)
     } else {
         message
     };
 
-    let mut parser = Parser::new(message.as_str(), &ParserOptions::default());
+    // Error locations are only recorded when capturing locations, which does not affect
+    // the message itself.
+    let parser_options = ParserOptions {
+        capture_location: true,
+        ..parser_options.clone()
+    };
+    let mut parser = Parser::new(message.as_str(), &parser_options);
 
     if let Err(e) = parser.parse() {
-        let is_literal_err = if let Some(message_path) = message_path {
-            if let JSXAttrValue::Lit(..) = message_path {
-                if message.contains("\\\\") {
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        } else {
-            false
-        };
-
-        #[cfg(feature = "plugin")]
-        let handler = &swc_core::plugin::errors::HANDLER;
-
-        #[cfg(feature = "custom_transform")]
-        let handler = &swc_core::common::errors::HANDLER;
+        let message_path = message_path.as_ref().expect("Should be available");
+        let is_literal_err =
+            matches!(message_path, JSXAttrValue::Lit(..)) && message.contains("\\\\");
 
         if is_literal_err {
-            #[cfg(any(feature = "plugin", feature = "custom_transform"))]
-            {
-                handler.with(|handler| {
-                    handler
-                        .struct_err(
-                            r#"
-                    [React Intl] Message failed to parse.
-                    It looks like `\\`s were used for escaping,
-                    this won't work with JSX string literals.
-                    Wrap with `{{}}`.
-                    See: http://facebook.github.io/react/docs/jsx-gotchas.html
-                    "#,
-                        )
-                        .emit()
-                });
-            }
+            emit_diagnostic_with(
+                on_error.parse,
+                Some(message_path.span()),
+                "[React Intl] Message failed to parse. It looks like `\\`s were used for \
+                 escaping, this won't work with JSX string literals. Wrap with `{}`.",
+                |diagnostic| {
+                    diagnostic.note("See: http://facebook.github.io/react/docs/jsx-gotchas.html");
+                },
+            );
         } else {
-            #[cfg(any(feature = "plugin", feature = "custom_transform"))]
-            {
-                handler.with(|handler| {
-                    handler
-                        .struct_warn(
-                            r#"
-                    [React Intl] Message failed to parse.
-                    See: https://formatjs.io/docs/core-concepts/icu-syntax
-                    \n {:#?}
-                    "#,
-                        )
-                        .emit();
-                    handler
-                        .struct_err(&format!("SyntaxError: {}", e.kind.to_string()))
-                        .emit()
-                });
-            }
+            let source_map = MessageSourceMap::from_jsx_attr_value(message_path, bindings);
+            emit_message_parse_error(
+                &e,
+                &message,
+                source_map,
+                preserve_whitespace,
+                message_path.span(),
+                on_error.parse,
+            );
         }
     }
 
@@ -446,145 +794,306 @@ fn get_jsx_icu_message_value(
 fn get_call_expr_icu_message_value(
     message_path: &Option<Expr>,
     preserve_whitespace: bool,
+    whitespace_options: &WhitespaceOptions,
+    parser_options: &ParserOptions,
+    bindings: &ConstBindings,
+    on_error: &OnErrorOptions,
 ) -> String {
     if message_path.is_none() {
         return "".to_string();
     }
 
-    let message =
-        get_call_expr_message_descriptor_value(message_path, Some(true)).unwrap_or("".to_string());
+    let message = get_call_expr_message_descriptor_value(
+        message_path,
+        Some(true),
+        bindings,
+        on_error.evaluation,
+    )
+    .unwrap_or("".to_string());
 
     let message = if !preserve_whitespace {
-        let message = WHITESPACE_REGEX.replace_all(&message, " ");
-        message.trim().to_string()
+        normalize_whitespace(&message, parser_options, whitespace_options)
     } else {
         message
     };
 
-    let mut parser = Parser::new(message.as_str(), &ParserOptions::default());
+    // Error locations are only recorded when capturing locations, which does not affect
+    // the message itself.
+    let parser_options = ParserOptions {
+        capture_location: true,
+        ..parser_options.clone()
+    };
+    let mut parser = Parser::new(message.as_str(), &parser_options);
 
     if let Err(e) = parser.parse() {
-        #[cfg(feature = "plugin")]
-        let handler = &swc_core::plugin::errors::HANDLER;
-
-        #[cfg(feature = "custom_transform")]
-        let handler = &swc_core::common::errors::HANDLER;
-
-        #[cfg(any(feature = "plugin", feature = "custom_transform"))]
-        {
-            handler.with(|handler| {
-                handler
-                    .struct_warn(
-                        r#"
-                    [React Intl] Message failed to parse.
-                    See: https://formatjs.io/docs/core-concepts/icu-syntax
-                    \n {:#?}
-                    "#,
-                    )
-                    .emit();
-                handler
-                    .struct_err(&format!("SyntaxError: {}", e.kind.to_string()))
-                    .emit()
-            });
-        }
+        let message_path = message_path.as_ref().expect("Should be available");
+        let source_map = MessageSourceMap::from_expr(message_path, bindings);
+        emit_message_parse_error(
+            &e,
+            &message,
+            source_map,
+            preserve_whitespace,
+            message_path.span(),
+            on_error.parse,
+        );
     }
 
     return message;
 }
 
-fn interpolate_name(resource_path: &str, name: &str, content: &str) -> Option<String> {
-    let filename = name;
-
-    let content = content;
-
-    let ext = "bin";
-    let basename = "file";
-    let directory = "";
-    let folder = "";
-    let query = "";
-
-    /*
-      if (resource_path) {
-      const parsed = path.parse(loaderContext.resourcePath)
-      let resourcePath = loaderContext.resourcePath
-
-      if (parsed.ext) {
-        ext = parsed.ext.slice(1)
-      }
-
-      if (parsed.dir) {
-        basename = parsed.name
-        resourcePath = parsed.dir + path.sep
-      }
-
-      if (typeof context !== 'undefined') {
-        directory = path
-          .relative(context, resourcePath + '_')
-          .replace(/\\/g, '/')
-          .replace(/\.\.(\/)?/g, '_$1')
-        directory = directory.slice(0, -1)
-      } else {
-        directory = resourcePath.replace(/\\/g, '/').replace(/\.\.(\/)?/g, '_$1')
-      }
-
-      if (directory.length === 1) {
-        directory = ''
-      } else if (directory.length > 1) {
-        folder = path.basename(directory)
-      }
-    }
-      */
-
-    let mut url = filename.to_string();
-    let r = Regexp::new(r#"\[(?:([^:\]]+):)?(?:hash|contenthash)(?::([a-z]+\d*))?(?::(\d+))?\]"#)
-        .unwrap();
-
-    url = r
-        .replace(url.as_str(), |cap: &Captures| {
-            let hash_type = cap.get(1);
-            let digest_type = cap.get(2);
-            let max_length = cap.get(3);
-
-            // TODO: support hashtype
-            let mut hasher = Sha512::new();
-            hasher.update(content.as_bytes());
-            let hash = hasher.finalize();
-            let base64_hash = Base64::encode_string(&hash);
-
-            if let Some(max_length) = max_length {
-                base64_hash[0..max_length.as_str().parse::<usize>().unwrap()].to_string()
+/// Report an ICU syntax error at its location in the source, mapped back through the
+/// string literals, template quasis and `const` bindings the message was evaluated from.
+/// Falls back to the span of the whole message value if the location cannot be mapped.
+fn emit_message_parse_error(
+    error: &ParserError,
+    message: &str,
+    source_map: Option<MessageSourceMap>,
+    preserve_whitespace: bool,
+    value_span: Span,
+    severity: DiagnosticSeverity,
+) {
+    let span = source_map
+        .and_then(|source_map| {
+            if preserve_whitespace {
+                Some(source_map)
             } else {
-                base64_hash
+                source_map.collapse_whitespace()
             }
         })
-        .to_string();
+        .zip(error.location.as_ref())
+        .and_then(|(source_map, location)| {
+            source_map.get_span(message, location.start.offset, location.end.offset)
+        })
+        .unwrap_or(value_span);
+
+    emit_diagnostic_with(
+        severity,
+        Some(span),
+        &format!(
+            "[React Intl] Message failed to parse: SyntaxError: {}",
+            error.kind
+        ),
+        |diagnostic| {
+            diagnostic.note("See: https://formatjs.io/docs/core-concepts/icu-syntax");
+        },
+    );
+}
+
+fn interpolate_name(
+    resource_path: &str,
+    name: &str,
+    content: &str,
+    options: &FormatJSPluginOptions,
+) -> Option<String> {
+    match interpolate::interpolate_name(
+        resource_path,
+        name,
+        content,
+        options.id_interpolate_root.as_deref(),
+    ) {
+        Ok(id) => Some(id),
+        Err(e) => {
+            emit_diagnostic(
+                options.on_error.id,
+                None,
+                &format!("[React Intl] Cannot interpolate id with `{}`: {}", name, e),
+            );
+
+            None
+        }
+    }
+}
+
+/// The text with runs of characters other than letters, digits, `_` and `-` replaced by
+/// `_`, to be used as part of an id.
+fn sanitize_id_part(text: &str) -> String {
+    let mut sanitized = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            sanitized.push(c);
+        } else if !sanitized.is_empty() && !sanitized.ends_with('_') {
+            sanitized.push('_');
+        }
+    }
+
+    sanitized.trim_end_matches('_').to_string()
+}
+
+/// Set an option overridden by a `@formatjs` comment of the file.
+fn set_overridable_option(
+    options: &mut FormatJSPluginOptions,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    fn parse<T: for<'de> Deserialize<'de>>(value: serde_json::Value) -> Result<T, String> {
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    match key {
+        "removeDefaultMessage" => options.remove_default_message = parse(value)?,
+        "idInterpolationPattern" | "idInterpolatePattern" => {
+            options.id_interpolate_pattern = parse(value)?
+        }
+        "idInterpolateRoot" => options.id_interpolate_root = parse(value)?,
+        "ast" => options.ast = parse(value)?,
+        "extractSourceLocation" => options.extract_source_location = parse(value)?,
+        "preserveWhitespace" => options.preserve_whitespace = parse(value)?,
+        _ => {
+            return Err(format!(
+                "expected one of {}",
+                OVERRIDABLE_OPTIONS
+                    .iter()
+                    .map(|key| format!("`{}`", key))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse the message into the array literal of its AST, for `ast` mode. Locations are
+/// left out like `formatjs compile --ast` does, regardless of `captureLocation`.
+fn create_ast_expr(message: &str, parser_options: &ParserOptions) -> Option<Expr> {
+    let parser_options = ParserOptions {
+        capture_location: false,
+        ..parser_options.clone()
+    };
+    let mut parser = Parser::new(message, &parser_options);
+    let parsed = parser.parse().ok()?;
+    let value = serde_json::to_value(&parsed).expect("Should be serializable");
+    Some(json_value_to_expr(&value))
+}
+
+/// Statically evaluate the fields of `descriptorKeys.extraFields` of a JSX element.
+/// Attributes without value are `true`.
+fn evaluate_jsx_extra_fields(
+    extra: &[(String, Option<JSXAttrValue>)],
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
+) -> BTreeMap<String, serde_json::Value> {
+    extra
+        .iter()
+        .filter_map(|(key, value)| {
+            let value = match value {
+                None => Some(serde_json::Value::Bool(true)),
+                Some(JSXAttrValue::Lit(Lit::Str(str))) => {
+                    Some(serde_json::Value::String(get_jsx_str_value(str)))
+                }
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) => evaluate_extra_field(expr, bindings, severity),
+                _ => None,
+            };
+            value.map(|value| (key.clone(), value))
+        })
+        .collect()
+}
+
+fn evaluate_call_expr_extra_fields(
+    extra: &[(String, Expr)],
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
+) -> BTreeMap<String, serde_json::Value> {
+    extra
+        .iter()
+        .filter_map(|(key, value)| {
+            evaluate_extra_field(value, bindings, severity).map(|value| (key.clone(), value))
+        })
+        .collect()
+}
+
+fn evaluate_extra_field(
+    expr: &Expr,
+    bindings: &ConstBindings,
+    severity: DiagnosticSeverity,
+) -> Option<serde_json::Value> {
+    evaluate_json_descriptor_value(
+        expr,
+        bindings,
+        severity,
+        "[React Intl] Extra descriptor fields must be statically evaluate-able for extraction.",
+    )
+}
+
+/// The content hashed into generated ids.
+fn get_id_content(default_message: &str, description: &Option<MessageDescriptionValue>) -> String {
+    if let Some(description) = description {
+        format!("{}#{}", default_message, description.to_content_string())
+    } else {
+        default_message.to_string()
+    }
+}
 
-    /*
-    url = url
-      .replace(/\[ext\]/gi, () => ext)
-      .replace(/\[name\]/gi, () => basename)
-      .replace(/\[path\]/gi, () => directory)
-      .replace(/\[folder\]/gi, () => folder)
-      .replace(/\[query\]/gi, () => query)
-    */
+/// Merge the description of a translator comment with the `description` of the
+/// descriptor. String descriptions are joined by a newline, while structured descriptions
+/// get the comment as `comment` unless they already have one.
+fn merge_comment_description(
+    description: Option<MessageDescriptionValue>,
+    comment_description: Option<&str>,
+) -> Option<MessageDescriptionValue> {
+    let comment_description = if let Some(comment_description) = comment_description {
+        comment_description
+    } else {
+        return description;
+    };
 
-    Some(url.to_string())
+    Some(match description {
+        None => MessageDescriptionValue::Str(comment_description.to_string()),
+        Some(MessageDescriptionValue::Obj(serde_json::Value::Object(mut values))) => {
+            values
+                .entry("comment")
+                .or_insert_with(|| comment_description.into());
+            MessageDescriptionValue::Obj(serde_json::Value::Object(values))
+        }
+        Some(description) => MessageDescriptionValue::Str(format!(
+            "{}\n{}",
+            description.to_content_string(),
+            comment_description
+        )),
+    })
 }
 
 // TODO: Consolidate with evaluate_call_expr_message_descriptor
 fn evaluate_jsx_message_descriptor(
     descriptor_path: &JSXMessageDescriptorPath,
     options: &FormatJSPluginOptions,
+    bindings: &ConstBindings,
     filename: &str,
+    comment_description: Option<&str>,
 ) -> MessageDescriptor {
-    let id = get_jsx_message_descriptor_value(&descriptor_path.id, None);
+    let id = get_jsx_message_descriptor_value(
+        &descriptor_path.id,
+        None,
+        bindings,
+        options.on_error.evaluation,
+    );
+    let generated_id = id.is_none();
     let default_message = get_jsx_icu_message_value(
         &descriptor_path.default_message,
         options.preserve_whitespace,
+        &options.whitespace,
+        &options.parser_options,
+        bindings,
+        &options.on_error,
     );
 
-    let description =
-        get_jsx_message_descriptor_value_maybe_object(&descriptor_path.description, None);
+    let description = merge_comment_description(
+        get_jsx_message_descriptor_value_maybe_object(
+            &descriptor_path.description,
+            None,
+            bindings,
+            options.on_error.evaluation,
+        ),
+        comment_description,
+    );
+    let extra = evaluate_jsx_extra_fields(
+        &descriptor_path.extra,
+        bindings,
+        options.on_error.evaluation,
+    );
 
     // Note: do not support override fn
     let id = if id.is_none() && default_message != "" {
@@ -592,20 +1101,12 @@ fn evaluate_jsx_message_descriptor(
         {
             interpolate_pattern.as_str()
         } else {
-            "[sha512:contenthash:base64:6]"
+            DEFAULT_ID_INTERPOLATE_PATTERN
         };
 
-        let content = if let Some(description) = &description {
-            if let MessageDescriptionValue::Str(description) = description {
-                format!("{}#{}", default_message, description)
-            } else {
-                default_message.clone()
-            }
-        } else {
-            default_message.clone()
-        };
+        let content = get_id_content(&default_message, &description);
 
-        interpolate_name(filename, interpolate_pattern, &content)
+        interpolate_name(filename, interpolate_pattern, &content, options)
     } else {
         id
     };
@@ -614,41 +1115,60 @@ fn evaluate_jsx_message_descriptor(
         id,
         default_message: Some(default_message),
         description,
+        generated_id,
+        short_id: None,
+        extra,
     }
 }
 
 fn evaluate_call_expr_message_descriptor(
     descriptor_path: &CallExprMessageDescriptorPath,
     options: &FormatJSPluginOptions,
+    bindings: &ConstBindings,
     filename: &str,
+    comment_description: Option<&str>,
 ) -> MessageDescriptor {
-    let id = get_call_expr_message_descriptor_value(&descriptor_path.id, None);
+    let id = get_call_expr_message_descriptor_value(
+        &descriptor_path.id,
+        None,
+        bindings,
+        options.on_error.evaluation,
+    );
+    let generated_id = id.is_none();
     let default_message = get_call_expr_icu_message_value(
         &descriptor_path.default_message,
         options.preserve_whitespace,
+        &options.whitespace,
+        &options.parser_options,
+        bindings,
+        &options.on_error,
     );
 
-    let description =
-        get_call_expr_message_descriptor_value_maybe_object(&descriptor_path.description, None);
+    let description = merge_comment_description(
+        get_call_expr_message_descriptor_value_maybe_object(
+            &descriptor_path.description,
+            None,
+            bindings,
+            options.on_error.evaluation,
+        ),
+        comment_description,
+    );
+    let extra = evaluate_call_expr_extra_fields(
+        &descriptor_path.extra,
+        bindings,
+        options.on_error.evaluation,
+    );
 
     let id = if id.is_none() && default_message != "" {
         let interpolate_pattern = if let Some(interpolate_pattern) = &options.id_interpolate_pattern
         {
             interpolate_pattern.as_str()
         } else {
-            "[sha512:contenthash:base64:6]"
+            DEFAULT_ID_INTERPOLATE_PATTERN
         };
 
-        let content = if let Some(description) = &description {
-            if let MessageDescriptionValue::Str(description) = description {
-                format!("{}#{}", default_message, description)
-            } else {
-                default_message.clone()
-            }
-        } else {
-            default_message.clone()
-        };
-        interpolate_name(filename, interpolate_pattern, &content)
+        let content = get_id_content(&default_message, &description);
+        interpolate_name(filename, interpolate_pattern, &content, options)
     } else {
         id
     };
@@ -657,28 +1177,42 @@ fn evaluate_call_expr_message_descriptor(
         id,
         default_message: Some(default_message),
         description,
+        generated_id,
+        short_id: None,
+        extra,
     }
 }
 
+/// Where a message is declared, and how problems with it are reported.
+struct MessageContext<'a> {
+    filename: &'a str,
+    span: Span,
+    /// Start and end of the descriptor, with `extractSourceLocation`.
+    location: Option<(Loc, Loc)>,
+    meta: &'a BTreeMap<String, String>,
+    on_error: &'a OnErrorOptions,
+}
+
 fn store_message(
     messages: &mut Vec<ExtractedMessage>,
+    message_ids: &mut HashMap<String, (usize, Span)>,
     descriptor: &MessageDescriptor,
-    filename: &str,
-    location: Option<(Loc, Loc)>,
+    context: MessageContext,
 ) {
-    if descriptor.id.is_none() && descriptor.default_message.is_none() {
-        #[cfg(feature = "plugin")]
-        let handler = &swc_core::plugin::errors::HANDLER;
+    let MessageContext {
+        filename,
+        span,
+        location,
+        meta,
+        on_error,
+    } = context;
 
-        #[cfg(feature = "custom_transform")]
-        let handler = &swc_core::common::errors::HANDLER;
-
-        #[cfg(any(feature = "plugin", feature = "custom_transform"))]
-        handler.with(|handler| {
-            handler
-                .struct_err("[React Intl] Message Descriptors require an `id` or `defaultMessage`.")
-                .emit()
-        });
+    if descriptor.id.is_none() && descriptor.default_message.is_none() {
+        emit_diagnostic(
+            on_error.descriptor,
+            Some(span),
+            "[React Intl] Message Descriptors require an `id` or `defaultMessage`.",
+        );
     }
 
     let source_location = if let Some(location) = location {
@@ -700,7 +1234,7 @@ fn store_message(
         None
     };
 
-    messages.push(ExtractedMessage {
+    let message = ExtractedMessage {
         id: descriptor
             .id
             .as_ref()
@@ -713,7 +1247,123 @@ fn store_message(
             .clone(),
         description: descriptor.description.clone(),
         loc: source_location,
-    });
+        short_id: descriptor.short_id.clone(),
+        meta: meta.clone(),
+        extra: descriptor.extra.clone(),
+    };
+
+    if !message.id.is_empty() {
+        if let Some(&(index, existing_span)) = message_ids.get(&message.id) {
+            // Identical descriptors are allowed to be declared multiple times,
+            // only extract them once.
+            if !messages[index].has_same_content(&message) {
+                emit_diagnostic_with(
+                    on_error.duplicate,
+                    Some(span),
+                    &format!(
+                        "[React Intl] Duplicate message id: \"{}\", but the `description` and/or `defaultMessage` are different.",
+                        message.id
+                    ),
+                    |diagnostic| {
+                        diagnostic.span_note(existing_span, "first declared here");
+                        if descriptor.generated_id {
+                            diagnostic.note(
+                                "The id is generated from the message content. Use a longer hash in `idInterpolationPattern` to avoid collisions.",
+                            );
+                        }
+                    },
+                );
+            }
+            return;
+        }
+
+        message_ids.insert(message.id.clone(), (messages.len(), span));
+    }
+
+    messages.push(message);
+}
+
+/// The `values` of a message component, if it's an object literal.
+fn get_jsx_values_object(attrs: &[JSXAttrOrSpread]) -> Option<&ObjectLit> {
+    attrs.iter().rev().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            name: JSXAttrName::Ident(name),
+            value:
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })),
+            ..
+        }) if &*name.sym == "values" => match &**expr {
+            Expr::Object(obj) => Some(obj),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// JSX attributes of the properties of a descriptor object, skipping the ones overridden by
+/// `later_attrs`. `None` if a property cannot be written as an attribute.
+fn create_jsx_attrs_from_object(
+    obj: &ObjectLit,
+    later_attrs: &[JSXAttrOrSpread],
+) -> Option<Vec<JSXAttrOrSpread>> {
+    let mut attrs = vec![];
+    for prop in &obj.props {
+        let (name, value) = match prop.as_prop().map(|prop| &**prop) {
+            Some(Prop::KeyValue(kv)) => (get_static_prop_name(&kv.key)?, (*kv.value).clone()),
+            Some(Prop::Shorthand(ident)) => (&*ident.sym, Expr::Ident(ident.clone())),
+            _ => return None,
+        };
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '-')
+        {
+            return None;
+        }
+
+        let is_overridden = later_attrs.iter().any(|attr| {
+            matches!(attr, JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(ident), .. }) if &*ident.sym == name)
+        });
+        if is_overridden {
+            continue;
+        }
+
+        attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+            span: DUMMY_SP,
+            name: JSXAttrName::Ident(Ident::new(name.into(), DUMMY_SP)),
+            value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                span: DUMMY_SP,
+                expr: JSXExpr::Expr(Box::new(value)),
+            })),
+        }));
+    }
+    Some(attrs)
+}
+
+fn is_id_prop(prop: &PropOrSpread, keys: &DescriptorKeysOptions) -> bool {
+    if let PropOrSpread::Prop(prop) = prop {
+        if let Prop::KeyValue(kv) = &**prop {
+            return get_message_descriptor_key_from_call_expr(&kv.key, keys) == Some("id");
+        }
+    }
+    false
+}
+
+/// Returns the id of a descriptor object reduced to `{id: "..."}`.
+fn get_id_from_id_only_descriptor(expr: &Expr, keys: &DescriptorKeysOptions) -> Option<String> {
+    if let Expr::Object(obj) = expr {
+        if let [prop] = obj.props.as_slice() {
+            if let Some(Prop::KeyValue(kv)) = prop.as_prop().map(|prop| &**prop) {
+                if let Expr::Lit(Lit::Str(id)) = &*kv.value {
+                    if is_id_prop(prop, keys) {
+                        return Some(id.value.to_string());
+                    }
+                }
+            }
+        }
+    }
+    None
 }
 
 fn get_message_object_from_expression(expr: Option<&mut ExprOrSpread>) -> Option<&mut Expr> {
@@ -725,7 +1375,11 @@ fn get_message_object_from_expression(expr: Option<&mut ExprOrSpread>) -> Option
     }
 }
 
-fn assert_object_expression(expr: &Option<&mut Expr>, callee: &Callee) {
+fn assert_object_expression(
+    expr: &Option<&mut Expr>,
+    callee: &Callee,
+    severity: DiagnosticSeverity,
+) {
     let assert_fail = match expr {
         Some(expr) => !expr.is_object(),
         _ => true,
@@ -742,25 +1396,16 @@ fn assert_object_expression(expr: &Option<&mut Expr>, callee: &Callee) {
             None
         };
 
-        #[cfg(feature = "plugin")]
-        let handler = &swc_core::plugin::errors::HANDLER;
-
-        #[cfg(feature = "custom_transform")]
-        let handler = &swc_core::common::errors::HANDLER;
-
-        #[cfg(any(feature = "plugin", feature = "custom_transform"))]
-        handler.with(|handler| {
-            handler
-                .struct_err(
-                    &(format!(
-                        r#"[React Intl] `{}` must be called with an object expression
+        emit_diagnostic(
+            severity,
+            Some(callee.span()),
+            &(format!(
+                r#"[React Intl] `{}` must be called with an object expression
                         with values that are React Intl Message Descriptors,
                         also defined as object expressions."#,
-                        prop.unwrap_or_default()
-                    )),
-                )
-                .emit()
-        });
+                prop.unwrap_or_default()
+            )),
+        );
     }
 }
 
@@ -773,6 +1418,23 @@ pub struct ExtractedMessage {
     pub default_message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub loc: Option<SourceLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_id: Option<String>,
+    /// Metadata of the pragma comments of the file the message is declared in.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+    /// Fields of `descriptorKeys.extraFields`.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl ExtractedMessage {
+    /// Whether both messages have the same `defaultMessage` and `description`.
+    pub fn has_same_content(&self, other: &ExtractedMessage) -> bool {
+        self.default_message == other.default_message
+            && serde_json::to_value(&self.description).ok()
+                == serde_json::to_value(&other.description).ok()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -798,12 +1460,36 @@ pub struct FormatJSVisitor<C: Clone + Comments, S: SourceMapper> {
     options: FormatJSPluginOptions,
     filename: String,
     messages: Vec<ExtractedMessage>,
-    meta: HashMap<String, String>,
+    // Index in `messages` and span of the message stored for each id.
+    message_ids: HashMap<String, (usize, Span)>,
+    meta: BTreeMap<String, String>,
+    bindings: ConstBindings,
+    imports: ImportResolver,
+    directives: FileDirectives,
+    format_message_scopes: FormatMessageScopes,
+    id_validation: Option<Regexp>,
+    translations: Option<Translations>,
+    // Problems of the plugin options, reported with the first module visited.
+    config_errors: Vec<String>,
+    short_ids: HashMap<String, String>,
     component_names: HashSet<String>,
     function_names: HashSet<String>,
+    module_source_names: HashSet<String>,
+    /// Local name of `FormattedMessage` to import, if children messages were rewritten
+    /// without it being imported.
+    formatted_message_import: Option<String>,
+    hardcoded_strings: Vec<HardcodedString>,
+    hardcoded_string_patterns: Vec<Regexp>,
+    /// Depth of JSX elements whose text is not checked for hardcoded strings.
+    untranslated_text_depth: usize,
 }
 
 impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
+    /// The hardcoded strings of the visited module reported with `hardcodedStrings`.
+    pub fn hardcoded_strings(&self) -> &[HardcodedString] {
+        &self.hardcoded_strings
+    }
+
     fn new(
         source_map: std::sync::Arc<S>,
         comments: C,
@@ -829,62 +1515,957 @@ impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
                 component_names.insert(name.to_string());
             });
 
+        let mut module_source_names: HashSet<String> = DEFAULT_MODULE_SOURCE_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        plugin_options
+            .additional_module_source_names
+            .iter()
+            .for_each(|name| {
+                module_source_names.insert(name.to_string());
+            });
+
+        let mut config_errors = vec![];
+
+        let id_validation = plugin_options
+            .id_strategy
+            .as_ref()
+            .and_then(|strategy| strategy.validate.as_ref())
+            .and_then(|pattern| match Regexp::new(pattern) {
+                Ok(regexp) => Some(regexp),
+                Err(e) => {
+                    config_errors.push(format!("Invalid `idStrategy.validate`: {}", e));
+                    None
+                }
+            });
+
+        let translations = plugin_options.translations.as_ref().map(|options| {
+            let (translations, errors) = Translations::load(options);
+            config_errors.extend(errors);
+            translations
+        });
+
+        let hardcoded_string_patterns = plugin_options
+            .hardcoded_strings
+            .iter()
+            .flat_map(|options| options.ignore_patterns.iter())
+            .filter_map(|pattern| match Regexp::new(pattern) {
+                Ok(regexp) => Some(regexp),
+                Err(e) => {
+                    config_errors.push(format!("Invalid `hardcodedStrings.ignorePatterns`: {}", e));
+                    None
+                }
+            })
+            .collect();
+
         FormatJSVisitor {
             source_map,
             comments,
             options: plugin_options,
             filename: filename.to_string(),
             messages: Default::default(),
+            message_ids: Default::default(),
             meta: Default::default(),
+            bindings: Default::default(),
+            imports: Default::default(),
+            directives: Default::default(),
+            format_message_scopes: Default::default(),
+            id_validation,
+            translations,
+            config_errors,
+            short_ids: Default::default(),
             component_names,
             function_names,
+            module_source_names,
+            formatted_message_import: None,
+            hardcoded_strings: Default::default(),
+            hardcoded_string_patterns,
+            untranslated_text_depth: 0,
         }
     }
-    fn read_pragma(&mut self, span_lo: BytePos, span_hi: BytePos) {
-        let mut comments = self.comments.get_leading(span_lo).unwrap_or_default();
-        comments.append(&mut self.comments.get_leading(span_hi).unwrap_or_default());
 
-        let pragma = self.options.pragma.as_str();
+    /// Whether the JSX element is one of the message components. Explicitly configured
+    /// `additionalComponentNames` always match, while `FormattedMessage` has to be imported
+    /// from one of the formatjs modules (possibly aliased or through a namespace import),
+    /// or not imported at all with `matchUnimportedNames`.
+    fn is_message_component(&self, name: &JSXElementName) -> bool {
+        match name {
+            JSXElementName::Ident(ident) => {
+                self.options
+                    .additional_component_names
+                    .iter()
+                    .any(|name| *name == *ident.sym)
+                    || self
+                        .resolve_unshadowed_name(&ident.sym)
+                        .map_or(false, |name| self.component_names.contains(name))
+            }
+            JSXElementName::JSXMemberExpr(JSXMemberExpr {
+                obj: JSXObject::Ident(obj),
+                prop,
+            }) => self.imports.is_namespace(&obj.sym) && self.component_names.contains(&*prop.sym),
+            _ => false,
+        }
+    }
 
-        for comment in comments {
-            let comment_text = &*comment.text;
-            if comment_text.contains(pragma) {
-                let value = comment_text.split(pragma).nth(1);
-                if let Some(value) = value {
-                    let value = WHITESPACE_REGEX.split(value.trim());
-                    for kv in value {
-                        let mut kv = kv.split(":");
-                        self.meta.insert(
-                            kv.next().unwrap().to_string(),
-                            kv.next().unwrap().to_string(),
-                        );
+    fn is_children_message_component(&self, name: &JSXElementName) -> bool {
+        match name {
+            JSXElementName::Ident(ident) => self
+                .options
+                .children_message_components
+                .iter()
+                .any(|name| *name == *ident.sym),
+            _ => false,
+        }
+    }
+
+    /// The element name referring to `FormattedMessage`, either through an existing import,
+    /// or an import added to the module.
+    fn get_formatted_message_name(&mut self) -> JSXElementName {
+        if let Some(local) = self.imports.find_local("FormattedMessage") {
+            return JSXElementName::Ident(Ident::new(local.into(), DUMMY_SP));
+        }
+        if let Some(namespace) = self.imports.find_namespace() {
+            return JSXElementName::JSXMemberExpr(JSXMemberExpr {
+                obj: JSXObject::Ident(Ident::new(namespace.into(), DUMMY_SP)),
+                prop: Ident::new("FormattedMessage".into(), DUMMY_SP),
+            });
+        }
+
+        // Pick a name no other binding of the module uses, like `_FormattedMessage2`.
+        let is_taken =
+            |name: &str| self.imports.is_imported(name) || self.bindings.is_declared(name);
+        let local = [
+            "FormattedMessage".to_string(),
+            "_FormattedMessage".to_string(),
+        ]
+        .into_iter()
+        .chain((2..).map(|index| format!("_FormattedMessage{}", index)))
+        .find(|name| !is_taken(name))
+        .expect("Should find a free name");
+        self.imports.declare(&local, "FormattedMessage");
+        self.formatted_message_import = Some(local.clone());
+        JSXElementName::Ident(Ident::new(local.into(), DUMMY_SP))
+    }
+
+    /// Rewrite `<T>Hello <b>{name}</b></T>` to
+    /// `<FormattedMessage defaultMessage="Hello <b>{name}</b>" values={{...}} />`, which is
+    /// then extracted like any other `FormattedMessage`.
+    fn rewrite_children_message(&mut self, element: &mut JSXElement) {
+        if element.children.is_empty() {
+            return;
+        }
+
+        let message = match ChildrenMessage::serialize(&element.children, &self.bindings) {
+            Ok(message) => message,
+            Err(span) => {
+                emit_diagnostic(self.options.on_error.evaluation, Some(span),
+                        "[React Intl] Message children must be text, expressions or elements to be extracted.");
+
+                return;
+            }
+        };
+
+        if message.message.trim().is_empty() {
+            return;
+        }
+
+        element.opening.name = self.get_formatted_message_name();
+        element.opening.self_closing = true;
+        element
+            .opening
+            .attrs
+            .push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+                span: DUMMY_SP,
+                name: JSXAttrName::Ident(Ident::new("defaultMessage".into(), DUMMY_SP)),
+                value: Some(create_jsx_str_attr_value(&message.message)),
+            }));
+        if !message.values.is_empty() {
+            element
+                .opening
+                .attrs
+                .push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+                    span: DUMMY_SP,
+                    name: JSXAttrName::Ident(Ident::new("values".into(), DUMMY_SP)),
+                    value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                        span: DUMMY_SP,
+                        expr: JSXExpr::Expr(Box::new(message.values_object())),
+                    })),
+                }));
+        }
+        element.children.clear();
+        element.closing = None;
+    }
+
+    /// Rewrite a message template to a `formatMessage` call with the extracted descriptor.
+    /// Returns `None` if the template is not tagged with one of `taggedTemplates.tags`.
+    fn rewrite_tagged_template(&mut self, tagged_tpl: &TaggedTpl) -> Option<Expr> {
+        // Tags are module bindings (i.e. imported) or globals, so local bindings of the same
+        // name are something else.
+        let is_message_tag = matches!(
+            &*tagged_tpl.tag,
+            Expr::Ident(tag) if self.options.tagged_templates.tags.iter().any(|name| *name == *tag.sym)
+                && !self.format_message_scopes.is_shadowed(&tag.sym)
+        );
+        if !is_message_tag || self.is_ignored_by_directive(tagged_tpl.span) {
+            return None;
+        }
+
+        let message = match TemplateMessage::from_tpl(&tagged_tpl.tpl) {
+            Ok(message) => message,
+            Err(span) => {
+                emit_diagnostic(
+                    self.options.on_error.evaluation,
+                    Some(span),
+                    "[React Intl] Message templates must not contain invalid escape sequences.",
+                );
+
+                return None;
+            }
+        };
+
+        let mut descriptor = Expr::Object(ObjectLit {
+            span: tagged_tpl.span,
+            props: vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(Ident::new("defaultMessage".into(), DUMMY_SP)),
+                value: Box::new(Expr::Lit(Lit::Str(Str {
+                    span: tagged_tpl.tpl.span,
+                    value: message.message.as_str().into(),
+                    raw: None,
+                }))),
+            })))],
+        });
+        self.process_message_object(&mut Some(&mut descriptor), None, tagged_tpl.span);
+
+        let mut args = vec![ExprOrSpread {
+            spread: None,
+            expr: Box::new(descriptor),
+        }];
+        if let Some(values) = message.values_object() {
+            args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(values),
+            });
+        }
+
+        let format_message = self
+            .options
+            .tagged_templates
+            .format_message
+            .as_deref()
+            .unwrap_or(DEFAULT_FORMAT_MESSAGE);
+
+        Some(Expr::Call(CallExpr {
+            span: tagged_tpl.span,
+            callee: Callee::Expr(Box::new(create_callee(format_message))),
+            args,
+            type_args: None,
+        }))
+    }
+
+    /// Resolve a reference to a descriptor declared in the module, i.e. `msgs.header`.
+    fn resolve_descriptor(&self, expr: &Expr) -> Option<ResolvedDescriptor> {
+        DescriptorResolver {
+            bindings: &self.bindings,
+            is_define_messages: |callee: &Callee| self.get_define_messages_name(callee).is_some(),
+        }
+        .resolve(expr)
+    }
+
+    /// Inline the spreads of a descriptor object, i.e. `{...base, defaultMessage}`.
+    fn flatten_descriptor(&self, obj: &ObjectLit) -> Option<ObjectLit> {
+        DescriptorResolver {
+            bindings: &self.bindings,
+            is_define_messages: |callee: &Callee| self.get_define_messages_name(callee).is_some(),
+        }
+        .flatten(obj)
+    }
+
+    /// The static `defaultMessage` of a resolved descriptor.
+    fn get_static_default_message(&self, obj: &ObjectLit) -> Option<String> {
+        obj.props
+            .iter()
+            .rev()
+            .find_map(|prop| match prop.as_prop().map(|prop| &**prop) {
+                Some(Prop::KeyValue(kv))
+                    if get_message_descriptor_key_from_call_expr(
+                        &kv.key,
+                        &self.options.descriptor_keys,
+                    ) == Some("defaultMessage") =>
+                {
+                    self.bindings.evaluate_string(&kv.value)
+                }
+                _ => None,
+            })
+    }
+
+    /// Inline spreads of descriptors declared as plain objects in the module into the
+    /// attributes of a message component, so they are extracted and transformed like
+    /// attributes. Returns the `defaultMessage` of a spread `defineMessages` descriptor,
+    /// which is extracted where it's declared.
+    fn inline_jsx_spread_descriptors(
+        &self,
+        jsx_opening_elem: &mut JSXOpeningElement,
+    ) -> Option<String> {
+        let mut define_messages_default_message = None;
+        let mut attrs = vec![];
+
+        for (index, attr) in jsx_opening_elem.attrs.iter().enumerate() {
+            let resolved = match attr {
+                JSXAttrOrSpread::SpreadElement(spread) => self.resolve_descriptor(&spread.expr),
+                _ => None,
+            };
+
+            match resolved {
+                Some(resolved) if resolved.origin == DescriptorOrigin::DefineMessages => {
+                    define_messages_default_message =
+                        self.get_static_default_message(&resolved.object);
+                    attrs.push(attr.clone());
+                }
+                Some(resolved) => {
+                    let later_attrs = &jsx_opening_elem.attrs[index + 1..];
+                    match create_jsx_attrs_from_object(&resolved.object, later_attrs) {
+                        Some(inlined) => attrs.extend(inlined),
+                        None => attrs.push(attr.clone()),
+                    }
+                }
+                None => attrs.push(attr.clone()),
+            }
+        }
+
+        jsx_opening_elem.attrs = attrs;
+        define_messages_default_message
+    }
+
+    /// Returns `defineMessage` or `defineMessages` if the callee resolves to one of them.
+    fn get_define_messages_name<'a>(&'a self, callee: &'a Callee) -> Option<&'a str> {
+        let name = match callee {
+            Callee::Expr(expr) => match &**expr {
+                Expr::Ident(ident) => self.resolve_unshadowed_name(&ident.sym),
+                Expr::Member(MemberExpr {
+                    obj,
+                    prop: MemberProp::Ident(prop),
+                    ..
+                }) => match &**obj {
+                    Expr::Ident(obj) if self.imports.is_namespace(&obj.sym) => Some(&*prop.sym),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+
+        name.filter(|name| *name == "defineMessage" || *name == "defineMessages")
+    }
+
+    fn is_format_message_ident(&self, ident: &Ident) -> bool {
+        if let Some(is_alias) = self.format_message_scopes.lookup(&ident.sym) {
+            return is_alias;
+        }
+
+        self.options
+            .additional_function_names
+            .iter()
+            .any(|name| *name == *ident.sym)
+            || self
+                .imports
+                .resolve_name(&ident.sym)
+                .map_or(false, |name| self.function_names.contains(name))
+    }
+
+    fn is_format_message_member(&self, member_expr: &MemberExpr) -> bool {
+        match &member_expr.prop {
+            MemberProp::Ident(ident) => self.function_names.contains(&*ident.sym),
+            MemberProp::Computed(computed) => match &*computed.expr {
+                Expr::Lit(Lit::Str(str)) => self.function_names.contains(&*str.value),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the expression refers to `formatMessage`, either directly as in
+    /// `intl.formatMessage`, `intl?.formatMessage` or `intl['formatMessage']`, bound as in
+    /// `intl.formatMessage.bind(intl)`, or through an alias declared in scope.
+    fn is_format_message_reference(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Ident(ident) => self.is_format_message_ident(ident),
+            Expr::Paren(paren) => self.is_format_message_reference(&paren.expr),
+            Expr::Member(member_expr) => self.is_format_message_member(member_expr),
+            Expr::OptChain(OptChainExpr {
+                base: OptChainBase::Member(member_expr),
+                ..
+            }) => self.is_format_message_member(member_expr),
+            Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                ..
+            }) => match &**callee {
+                Expr::Member(MemberExpr {
+                    obj,
+                    prop: MemberProp::Ident(prop),
+                    ..
+                }) if &*prop.sym == "bind" => self.is_format_message_reference(obj),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the API an identifier refers to like `ImportResolver::resolve_name`, unless
+    /// it's shadowed by a binding declared in the scopes the visitor is in.
+    fn resolve_unshadowed_name<'a>(&'a self, local: &'a str) -> Option<&'a str> {
+        if self.format_message_scopes.lookup(local).is_some() {
+            return None;
+        }
+        self.imports.resolve_name(local)
+    }
+
+    /// Whether a local binding of the name is taken for `formatMessage` by its name alone,
+    /// which is only done with `matchUnimportedNames`.
+    fn is_function_name_binding(&self, name: &str) -> bool {
+        self.options.match_unimported_names && self.function_names.contains(name)
+    }
+
+    /// Record the declarations of a block in its scope before it's visited, so that they
+    /// shadow outer bindings from the start of the block, like functions and `const` do.
+    /// `var` declarations of nested blocks are only recorded when they are visited.
+    fn declare_hoisted<'a>(&mut self, stmts: impl Iterator<Item = &'a Stmt>) {
+        for stmt in stmts {
+            if let Stmt::Decl(decl) = stmt {
+                self.declare_decl(decl);
+            }
+        }
+    }
+
+    fn declare_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Fn(FnDecl { ident, .. }) => {
+                let is_alias = self.is_function_name_binding(&ident.sym);
+                self.format_message_scopes
+                    .declare(ident.sym.to_string(), is_alias);
+            }
+            Decl::Class(class_decl) => self
+                .format_message_scopes
+                .declare(class_decl.ident.sym.to_string(), false),
+            Decl::Var(var_decl) => self.declare_var_decl(var_decl),
+            _ => {}
+        }
+    }
+
+    /// Record the bindings of a variable declaration, marking the ones initialized with
+    /// `formatMessage` itself as aliases, i.e. `const t = intl.formatMessage`.
+    fn declare_var_decl(&mut self, var_decl: &VarDecl) {
+        for decl in &var_decl.decls {
+            match (&decl.name, &decl.init) {
+                (Pat::Ident(ident), Some(init)) if self.is_format_message_reference(init) => {
+                    self.format_message_scopes
+                        .declare(ident.id.sym.to_string(), true);
+                }
+                (pat, _) => self.declare_pat(pat),
+            }
+        }
+    }
+
+    /// Record the bindings declared by a pattern in the current scope, marking the ones
+    /// which alias `formatMessage`.
+    fn declare_pat(&mut self, pat: &Pat) {
+        let mut bindings = vec![];
+        collect_pat_bindings(
+            pat,
+            &|name| self.function_names.contains(name),
+            &mut bindings,
+        );
+        for (name, is_alias) in bindings {
+            let is_alias = is_alias || self.is_function_name_binding(&name);
+            self.format_message_scopes.declare(name, is_alias);
+        }
+    }
+
+    /// Whether the descriptor at the span is skipped by a `formatjs-ignore-next-line` or
+    /// `formatjs-disable` comment.
+    fn is_ignored_by_directive(&self, span: Span) -> bool {
+        !span.is_dummy()
+            && self
+                .directives
+                .is_ignored(span.lo, self.source_map.lookup_char_pos(span.lo).line)
+    }
+
+    /// Whether the children and attributes of the element are not checked for hardcoded
+    /// strings, because they're translated or the component is ignored.
+    fn is_untranslated_text_ignored(&self, name: &JSXElementName) -> bool {
+        self.is_message_component(name)
+            || self.is_children_message_component(name)
+            || self
+                .options
+                .hardcoded_strings
+                .as_ref()
+                .is_some_and(|options| options.ignore_components.contains(&get_element_name(name)))
+    }
+
+    /// Whether the value of the attribute is checked for hardcoded strings.
+    fn is_checked_attribute(&self, name: &str) -> bool {
+        self.options
+            .hardcoded_strings
+            .as_ref()
+            .is_some_and(|options| {
+                (DEFAULT_CHECKED_ATTRIBUTES.contains(&name)
+                    || options.attributes.iter().any(|attribute| attribute == name))
+                    && !options
+                        .ignore_attributes
+                        .iter()
+                        .any(|attribute| attribute == name)
+            })
+    }
+
+    /// Report user-facing text outside of message components as a hardcoded string.
+    fn report_hardcoded_string(&mut self, text: &str, attribute: Option<String>, span: Span) {
+        let severity = match &self.options.hardcoded_strings {
+            Some(options) => options.severity,
+            None => return,
+        };
+
+        let text = text.trim();
+        if self.untranslated_text_depth > 0
+            || span.is_dummy()
+            || !is_user_facing(text)
+            || self.is_ignored_by_directive(span)
+        {
+            return;
+        }
+        if self
+            .hardcoded_string_patterns
+            .iter()
+            .any(|pattern| pattern.is_match(text))
+        {
+            return;
+        }
+
+        let start = self.source_map.lookup_char_pos(span.lo);
+        let end = self.source_map.lookup_char_pos(span.hi);
+        self.hardcoded_strings.push(HardcodedString {
+            text: text.to_string(),
+            attribute: attribute.clone(),
+            loc: SourceLocation {
+                file: self.filename.clone(),
+                start: Location {
+                    line: start.line,
+                    col: start.col.to_usize(),
+                },
+                end: Location {
+                    line: end.line,
+                    col: end.col.to_usize(),
+                },
+            },
+        });
+
+        let message = match &attribute {
+            Some(attribute) => format!(
+                "[React Intl] Hardcoded string \"{}\" of `{}` is not translated.",
+                text, attribute
+            ),
+            None => format!(
+                "[React Intl] Hardcoded string \"{}\" is not translated.",
+                text
+            ),
+        };
+        emit_diagnostic(severity, Some(span), &message);
+    }
+
+    /// The description of the translator comment leading the descriptor at the span, or
+    /// the node it belongs to, like the `defineMessage` call or the `defineMessages`
+    /// property.
+    fn get_comment_description(&self, span: Span, node_span: Span) -> Option<String> {
+        let markers = &self.options.description_comment_markers;
+        if markers.is_empty() {
+            return None;
+        }
+
+        [span, node_span]
+            .iter()
+            .filter(|span| !span.is_dummy())
+            .find_map(|span| comment_description(&self.comments, span.lo, markers))
+    }
+
+    /// Apply the `@formatjs` option overrides of the file.
+    fn apply_option_overrides(&mut self) {
+        for option_override in &self.directives.option_overrides {
+            let result = set_overridable_option(
+                &mut self.options,
+                &option_override.key,
+                option_override.json_value(),
+            );
+
+            if let Err(e) = result {
+                emit_diagnostic(
+                    self.options.on_error.directive,
+                    Some(option_override.span),
+                    &format!(
+                        "[React Intl] Invalid `@formatjs` option `{}`: {}",
+                        option_override.key, e
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Apply `idStrategy` to the evaluated descriptor. Returns whether the id written in
+    /// the source has been replaced.
+    fn apply_id_strategy(
+        &self,
+        descriptor: &mut MessageDescriptor,
+        key: Option<&str>,
+        span: Span,
+    ) -> bool {
+        let strategy = if let Some(strategy) = &self.options.id_strategy {
+            strategy
+        } else {
+            return false;
+        };
+
+        let source_id = if descriptor.generated_id {
+            None
+        } else {
+            descriptor.id.clone()
+        };
+
+        if let (Some(id), Some(validation)) = (&source_id, &self.id_validation) {
+            if !validation.is_match(id) {
+                emit_diagnostic(
+                    self.options.on_error.id,
+                    Some(span),
+                    &format!(
+                        "[React Intl] Message id \"{}\" does not match `{}`.",
+                        id,
+                        validation.as_str()
+                    ),
+                );
+            }
+        }
+
+        // Ids written in the source are kept as is, unless `overrideExisting` is set.
+        if source_id.is_some() && !strategy.override_existing {
+            return false;
+        }
+
+        let default_message = descriptor.default_message.clone().unwrap_or_default();
+        if !default_message.is_empty() {
+            let pattern = strategy
+                .template
+                .as_deref()
+                .or(self.options.id_interpolate_pattern.as_deref())
+                .unwrap_or(DEFAULT_ID_INTERPOLATE_PATTERN);
+            let description = descriptor
+                .description
+                .as_ref()
+                .map(MessageDescriptionValue::to_content_string)
+                .unwrap_or_default();
+
+            // Substitute the placeholders of the descriptor before interpolating the pattern,
+            // so that the key and the source id are hashed along with the message when the
+            // template uses them.
+            let mut content = get_id_content(&default_message, &descriptor.description);
+            let mut pattern = pattern.to_string();
+            for (placeholder, value) in [
+                ("[key]", key.unwrap_or_default()),
+                ("[id]", source_id.as_deref().unwrap_or_default()),
+            ] {
+                if pattern.contains(placeholder) {
+                    pattern = pattern.replace(placeholder, value);
+                    content = format!("{}#{}", content, value);
+                }
+            }
+
+            // The description is substituted last, so that it's not interpolated itself.
+            descriptor.id = interpolate_name(&self.filename, &pattern, &content, &self.options)
+                .map(|id| id.replace("[description]", &sanitize_id_part(&description)));
+            descriptor.generated_id = true;
+        }
+
+        if strategy.prefix_by_directory {
+            if let Some(id) = &descriptor.id {
+                if let Some(prefix) = self.get_directory_prefix(span) {
+                    descriptor.id = Some(format!("{}.{}", prefix, id));
+                }
+            }
+        }
+
+        source_id.is_some() && descriptor.id != source_id
+    }
+
+    /// Returns the directory of the file relative to `idInterpolateRoot`, separated by `.`,
+    /// for `idStrategy.prefixByDirectory`. Absolute filenames require `idInterpolateRoot`, so
+    /// that ids do not depend on where the project is checked out.
+    fn get_directory_prefix(&self, span: Span) -> Option<String> {
+        let filename = self.filename.replace('\\', "/");
+        let is_absolute = filename.starts_with('/')
+            || filename
+                .as_bytes()
+                .get(1..3)
+                .is_some_and(|drive| drive == b":/");
+        if is_absolute && self.options.id_interpolate_root.is_none() {
+            emit_diagnostic(
+                self.options.on_error.id,
+                Some(span),
+                "[React Intl] `idStrategy.prefixByDirectory` requires `idInterpolateRoot` \
+                 for absolute filenames.",
+            );
+            return None;
+        }
+
+        let directory = interpolate_name(&self.filename, "[path]", "", &self.options)?;
+        let prefix = directory.trim_matches('/').replace('/', ".");
+        if prefix.is_empty() {
+            None
+        } else {
+            Some(prefix)
+        }
+    }
+
+    /// Returns the message to write in place of `defaultMessage`: the translation if
+    /// translations are inlined, otherwise the evaluated `defaultMessage`.
+    fn get_inlined_message(&self, descriptor: &MessageDescriptor, span: Span) -> Option<String> {
+        let translations = if let Some(translations) = &self.translations {
+            translations
+        } else {
+            return descriptor.default_message.clone();
+        };
+        let id = if let Some(id) = &descriptor.id {
+            id
+        } else {
+            return descriptor.default_message.clone();
+        };
+
+        let options = self
+            .options
+            .translations
+            .as_ref()
+            .expect("Translations should be configured");
+
+        if let Some((locale, message)) = translations.get(id) {
+            // Inline the source message rather than a translation which cannot be formatted,
+            // or turned into an AST.
+            let mut parser = Parser::new(message, &self.options.parser_options);
+            return match parser.parse() {
+                Ok(..) => Some(message.to_string()),
+                Err(e) => {
+                    emit_diagnostic(
+                        self.options.on_error.parse,
+                        Some(span),
+                        &format!(
+                            "[React Intl] Translation of \"{}\" for `{}` failed to parse: SyntaxError: {}",
+                            id, locale, e.kind
+                        ),
+                    );
+                    descriptor.default_message.clone()
+                }
+            };
+        }
+        let message = format!(
+            "[React Intl] Missing translation of \"{}\" for `{}`.",
+            id, options.locale
+        );
+
+        emit_diagnostic(options.on_missing, Some(span), &message);
+
+        descriptor.default_message.clone()
+    }
+
+    /// Assign the short id of `idOnly.shortIdLength` to the descriptor.
+    fn assign_short_id(&mut self, descriptor: &mut MessageDescriptor, span: Span) {
+        let length = if let Some(length) = self
+            .options
+            .id_only
+            .as_ref()
+            .and_then(|id_only| id_only.short_id_length)
+        {
+            length
+        } else {
+            return;
+        };
+        let id = if let Some(id) = &descriptor.id {
+            id
+        } else {
+            return;
+        };
+
+        let short_id = get_hash_digest(id, Some("xxhash64"), Some("base62"), Some(length))
+            .expect("Should be supported digest");
+
+        match self.short_ids.get(&short_id) {
+            Some(existing) if existing != id => {
+                emit_diagnostic(
+                    self.options.on_error.id,
+                    Some(span),
+                    &format!(
+                        "[React Intl] Short id \"{}\" of \"{}\" collides with \"{}\", \
+                             use a longer `shortIdLength`.",
+                        short_id, id, existing
+                    ),
+                );
+            }
+            _ => {
+                self.short_ids.insert(short_id.clone(), id.clone());
+            }
+        }
+
+        descriptor.short_id = Some(short_id);
+    }
+
+    fn process_format_message_args(&mut self, args: &mut [ExprOrSpread], span: Span) {
+        let message_descriptor = args.get_mut(0);
+        if let Some(message_descriptor) = message_descriptor {
+            // Resolve references to descriptors of the module, i.e. `formatMessage(msgs.foo)`.
+            // Plain objects are inlined to be extracted and transformed at the call site,
+            // while `defineMessages` descriptors are extracted where they are declared.
+            if message_descriptor.spread.is_none() && !message_descriptor.expr.is_object() {
+                if let Some(resolved) = self.resolve_descriptor(&message_descriptor.expr) {
+                    if resolved.origin == DescriptorOrigin::DefineMessages {
+                        let default_message = self.get_static_default_message(&resolved.object);
+                        if let (Some(default_message), Some(ExprOrSpread { spread: None, expr })) =
+                            (default_message, args.get(1))
+                        {
+                            if let Expr::Object(values) = &**expr {
+                                self.check_message_values(&default_message, values, values.span);
+                            }
+                        }
+                        return;
                     }
+
+                    *message_descriptor.expr = Expr::Object(ObjectLit {
+                        span: message_descriptor.expr.span(),
+                        ..resolved.object
+                    });
                 }
             }
+
+            if message_descriptor.expr.is_object() {
+                let default_message = self.process_message_object(
+                    &mut Some(message_descriptor.expr.as_mut()),
+                    None,
+                    span,
+                );
+
+                if let (Some(default_message), Some(ExprOrSpread { spread: None, expr })) =
+                    (default_message, args.get(1))
+                {
+                    if let Expr::Object(values) = &**expr {
+                        self.check_message_values(&default_message, values, values.span);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Report `values` of a message component which do not match the source message. Values
+    /// cannot be checked if the props are spread.
+    fn check_jsx_message_values(&self, message: &str, jsx_opening_elem: &JSXOpeningElement) {
+        let has_spread = jsx_opening_elem
+            .attrs
+            .iter()
+            .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(..)));
+        if has_spread {
+            return;
+        }
+
+        if let Some(values) = get_jsx_values_object(&jsx_opening_elem.attrs) {
+            self.check_message_values(message, values, jsx_opening_elem.span());
+        }
+    }
+
+    /// Report `values` which do not match the arguments and tags of the source message.
+    fn check_message_values(&self, message: &str, values: &ObjectLit, span: Span) {
+        let mut parser = Parser::new(message, &self.options.parser_options);
+        let ast = match parser.parse() {
+            Ok(ast) => ast,
+            // Reported when the descriptor is evaluated.
+            Err(..) => return,
+        };
+
+        let default_rich_text_elements = self.options.default_rich_text_elements.as_deref();
+        for issue in check_values(&ast, values, default_rich_text_elements) {
+            emit_diagnostic(
+                self.options.on_error.values,
+                Some(issue.span().unwrap_or(span)),
+                &issue.message(),
+            );
         }
     }
 
-    fn process_message_object(&mut self, message_descriptor: &mut Option<&mut Expr>) {
+    /// Read the metadata of the pragma comments of the file, reporting malformed entries.
+    fn read_pragma(&mut self, comments: &[Comment]) {
+        for comment in comments {
+            let parsed = if let Some(parsed) = parse_pragma(comment, &self.options.pragma) {
+                parsed
+            } else {
+                continue;
+            };
+
+            self.meta.extend(parsed.entries);
+
+            for e in parsed.errors {
+                emit_diagnostic(
+                    self.options.on_error.directive,
+                    Some(e.span),
+                    &format!(
+                        "[React Intl] Invalid `{}` pragma: {}",
+                        self.options.pragma, e.message
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Extract and transform the descriptor object of the node at `node_span`. Returns the
+    /// source `defaultMessage` of the descriptor, if any.
+    fn process_message_object(
+        &mut self,
+        message_descriptor: &mut Option<&mut Expr>,
+        key: Option<&str>,
+        node_span: Span,
+    ) -> Option<String> {
         if let Some(message_obj) = &mut *message_descriptor {
-            let (lo, hi) = (message_obj.span().lo, message_obj.span().hi);
+            let span = message_obj.span();
+            let (lo, hi) = (span.lo, span.hi);
+
+            if self.is_ignored_by_directive(span) {
+                return None;
+            }
 
             if let Expr::Object(obj) = *message_obj {
+                if obj.props.iter().any(PropOrSpread::is_spread) {
+                    if let Some(flattened) = self.flatten_descriptor(obj) {
+                        obj.props = flattened.props;
+                    }
+                }
+
                 let properties = &obj.props;
 
-                let descriptor_path = create_message_descriptor_from_call_expr(properties);
+                let descriptor_path = create_message_descriptor_from_call_expr(
+                    properties,
+                    &self.options.descriptor_keys,
+                );
 
                 // If the message is already compiled, don't re-compile it
                 if let Some(default_message) = &descriptor_path.default_message {
                     if default_message.is_array() {
-                        return;
+                        return None;
                     }
                 }
 
-                let descriptor = evaluate_call_expr_message_descriptor(
+                let mut descriptor = evaluate_call_expr_message_descriptor(
                     &descriptor_path,
                     &self.options,
+                    &self.bindings,
                     &self.filename,
+                    self.get_comment_description(span, node_span).as_deref(),
                 );
+                let default_message = descriptor
+                    .default_message
+                    .clone()
+                    .filter(|message| !message.is_empty());
+                let is_id_overridden = self.apply_id_strategy(&mut descriptor, key, span);
+                let inlined_message = self.get_inlined_message(&descriptor, span);
+                self.assign_short_id(&mut descriptor, span);
 
                 let source_location = if self.options.extract_source_location {
                     Some((
@@ -897,36 +2478,38 @@ impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
 
                 store_message(
                     &mut self.messages,
+                    &mut self.message_ids,
                     &descriptor,
-                    &self.filename,
-                    source_location,
+                    MessageContext {
+                        filename: &self.filename,
+                        span,
+                        location: source_location,
+                        meta: &self.meta,
+                        on_error: &self.options.on_error,
+                    },
                 );
 
                 let first_prop = properties.first().is_some();
 
                 // Insert ID potentially 1st before removing nodes
-                let id_prop = obj.props.iter().find(|prop| {
-                    if let PropOrSpread::Prop(prop) = prop {
-                        if let Prop::KeyValue(kv) = &**prop {
-                            return match &kv.key {
-                                PropName::Ident(ident) => &*ident.sym == "id",
-                                PropName::Str(str_) => &*str_.value == "id",
-                                _ => false,
-                            };
-                        }
-                    }
-                    false
-                });
+                let id_prop = obj
+                    .props
+                    .iter_mut()
+                    .find(|prop| is_id_prop(prop, &self.options.descriptor_keys));
 
-                if let Some(descriptor_id) = descriptor.id {
+                if let Some(descriptor_id) = descriptor.short_id.clone().or(descriptor.id) {
                     if let Some(id_prop) = id_prop {
-                        let prop = id_prop.as_prop().unwrap();
-                        let kv = &mut prop.as_key_value().unwrap();
-                        kv.to_owned().value = Box::new(Expr::Lit(Lit::Str(Str {
-                            span: DUMMY_SP,
-                            value: descriptor_id.into(),
-                            raw: None,
-                        })));
+                        if is_id_overridden || descriptor.short_id.is_some() {
+                            if let PropOrSpread::Prop(prop) = id_prop {
+                                if let Prop::KeyValue(kv) = &mut **prop {
+                                    *kv.value = Expr::Lit(Lit::Str(Str {
+                                        span: DUMMY_SP,
+                                        value: descriptor_id.into(),
+                                        raw: None,
+                                    }));
+                                }
+                            }
+                        }
                     } else {
                         obj.props.insert(
                             0,
@@ -947,7 +2530,10 @@ impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
                     match prop {
                         PropOrSpread::Prop(mut prop) => {
                             if let Prop::KeyValue(keyvalue) = &mut *prop {
-                                let key = get_message_descriptor_key_from_call_expr(&keyvalue.key);
+                                let key = get_message_descriptor_key_from_call_expr(
+                                    &keyvalue.key,
+                                    &self.options.descriptor_keys,
+                                );
                                 if let Some(key) = key {
                                     match key {
                                         "description" => {
@@ -964,25 +2550,14 @@ impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
                                                 // remove defaultMessage
                                             } else {
                                                 if let Some(descriptor_default_message) =
-                                                    descriptor.default_message.as_ref()
+                                                    inlined_message.as_ref()
                                                 {
                                                     if self.options.ast {
-                                                        let mut parser = Parser::new(
+                                                        if let Some(ast) = create_ast_expr(
                                                             descriptor_default_message,
-                                                            &ParserOptions::new(
-                                                                false, false, false, false, None,
-                                                            ),
-                                                        );
-                                                        if let Ok(parsed) = parser.parse() {
-                                                            let s = serde_json::to_string(&parsed)
-                                                                .unwrap();
-                                                            keyvalue.value = Box::new(Expr::Lit(
-                                                                Lit::Str(Str {
-                                                                    span: DUMMY_SP,
-                                                                    value: s.into(),
-                                                                    raw: None,
-                                                                }),
-                                                            ));
+                                                            &self.options.parser_options,
+                                                        ) {
+                                                            *keyvalue.value = ast;
                                                         }
                                                     } else {
                                                         keyvalue.value =
@@ -999,6 +2574,12 @@ impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
                                                 props.push(PropOrSpread::Prop(prop));
                                             }
                                         }
+                                        key if self.options.descriptor_keys.remove_extra_fields
+                                            && descriptor.extra.contains_key(key) =>
+                                        {
+                                            // remove extracted extra field
+                                            self.comments.take_leading(prop.span().lo);
+                                        }
                                         _ => props.push(PropOrSpread::Prop(prop)),
                                     }
                                 } else {
@@ -1013,27 +2594,120 @@ impl<C: Clone + Comments, S: SourceMapper> FormatJSVisitor<C, S> {
                 }
 
                 obj.props = props;
+
+                if self.options.id_only.is_some() {
+                    obj.props
+                        .retain(|prop| is_id_prop(prop, &self.options.descriptor_keys));
+                }
+
+                return default_message;
             }
         }
+
+        None
     }
 }
 
 impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
     noop_visit_mut_type!();
 
+    fn visit_mut_jsx_element(&mut self, element: &mut JSXElement) {
+        if self.is_children_message_component(&element.opening.name)
+            && !self.is_ignored_by_directive(element.opening.span)
+        {
+            self.rewrite_children_message(element);
+        }
+
+        let is_untranslated_text_ignored = self.options.hardcoded_strings.is_some()
+            && self.is_untranslated_text_ignored(&element.opening.name);
+        if is_untranslated_text_ignored {
+            self.untranslated_text_depth += 1;
+        }
+
+        element.visit_mut_children_with(self);
+
+        if is_untranslated_text_ignored {
+            self.untranslated_text_depth -= 1;
+        }
+    }
+
+    fn visit_mut_jsx_element_child(&mut self, child: &mut JSXElementChild) {
+        if self.options.hardcoded_strings.is_none() {
+            child.visit_mut_children_with(self);
+            return;
+        }
+
+        match child {
+            JSXElementChild::JSXText(text) => {
+                self.report_hardcoded_string(
+                    &clean_jsx_text(&get_jsx_text_value(text)),
+                    None,
+                    text.span,
+                );
+            }
+            JSXElementChild::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            }) => {
+                if let Some(text) = get_static_text(expr) {
+                    self.report_hardcoded_string(text, None, expr.span());
+                }
+            }
+            _ => {}
+        }
+
+        child.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_jsx_attr(&mut self, attr: &mut JSXAttr) {
+        let name = get_attr_name(&attr.name);
+        if self.is_checked_attribute(&name) {
+            match &attr.value {
+                Some(JSXAttrValue::Lit(Lit::Str(str))) => {
+                    self.report_hardcoded_string(&get_jsx_str_value(str), Some(name), str.span);
+                }
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) => {
+                    if let Some(text) = get_static_text(expr) {
+                        self.report_hardcoded_string(text, Some(name), expr.span());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        attr.visit_mut_children_with(self);
+    }
+
     fn visit_mut_jsx_opening_element(&mut self, jsx_opening_elem: &mut JSXOpeningElement) {
         jsx_opening_elem.visit_mut_children_with(self);
 
-        let name = &jsx_opening_elem.name;
+        if !self.is_message_component(&jsx_opening_elem.name)
+            || self.is_ignored_by_directive(jsx_opening_elem.span())
+        {
+            return;
+        }
 
-        if let JSXElementName::Ident(ident) = name {
-            if !self.component_names.contains(&*ident.sym) {
-                return;
+        let spread_default_message = self.inline_jsx_spread_descriptors(jsx_opening_elem);
+
+        let descriptor_path = create_message_descriptor_from_jsx_attr(
+            &jsx_opening_elem.attrs,
+            &self.options.descriptor_keys,
+        );
+
+        // A spread `defineMessages` descriptor is extracted where it's declared, only
+        // check the values passed with it.
+        if let (None, Some(default_message)) =
+            (&descriptor_path.default_message, spread_default_message)
+        {
+            if let Some(values) = get_jsx_values_object(&jsx_opening_elem.attrs) {
+                self.check_message_values(&default_message, values, jsx_opening_elem.span());
             }
+            return;
         }
 
-        let descriptor_path = create_message_descriptor_from_jsx_attr(&jsx_opening_elem.attrs);
-
         // In order for a default message to be extracted when
         // declaring a JSX element, it must be done with standard
         // `key=value` attributes. But it's completely valid to
@@ -1044,10 +2718,31 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
             return;
         }
 
+        // If the message is already compiled, don't re-compile it
+        if let Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+            expr: JSXExpr::Expr(expr),
+            ..
+        })) = &descriptor_path.default_message
+        {
+            if expr.is_array() {
+                return;
+            }
+        }
+
         // Evaluate the Message Descriptor values in a JSX
         // context, then store it.
-        let descriptor =
-            evaluate_jsx_message_descriptor(&descriptor_path, &self.options, &self.filename);
+        let mut descriptor = evaluate_jsx_message_descriptor(
+            &descriptor_path,
+            &self.options,
+            &self.bindings,
+            &self.filename,
+            self.get_comment_description(jsx_opening_elem.span(), DUMMY_SP)
+                .as_deref(),
+        );
+        let is_id_overridden =
+            self.apply_id_strategy(&mut descriptor, None, jsx_opening_elem.span());
+        let inlined_message = self.get_inlined_message(&descriptor, jsx_opening_elem.span());
+        self.assign_short_id(&mut descriptor, jsx_opening_elem.span());
 
         let source_location = if self.options.extract_source_location {
             Some((
@@ -1060,31 +2755,42 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
 
         store_message(
             &mut self.messages,
+            &mut self.message_ids,
             &descriptor,
-            &self.filename,
-            source_location,
+            MessageContext {
+                filename: &self.filename,
+                span: jsx_opening_elem.span(),
+                location: source_location,
+                meta: &self.meta,
+                on_error: &self.options.on_error,
+            },
         );
 
-        let id_attr = jsx_opening_elem.attrs.iter().find(|attr| match attr {
+        if let Some(default_message) = descriptor
+            .default_message
+            .as_deref()
+            .filter(|message| !message.is_empty())
+        {
+            self.check_jsx_message_values(default_message, jsx_opening_elem);
+        }
+
+        let first_attr = jsx_opening_elem.attrs.first().is_some();
+
+        let id_attr = jsx_opening_elem.attrs.iter_mut().find(|attr| match attr {
             JSXAttrOrSpread::JSXAttr(attr) => {
-                if let JSXAttrName::Ident(ident) = &attr.name {
-                    return &*ident.sym == "id";
-                } else {
-                    false
-                }
+                get_message_descriptor_key_from_jsx(&attr.name, &self.options.descriptor_keys)
+                    == "id"
             }
             _ => false,
         });
 
-        let first_attr = jsx_opening_elem.attrs.first().is_some();
-
-        // Do not support overrideIdFn, only support idInterpolatePattern
-        if descriptor.id.is_some() {
+        // Do not support overrideIdFn, only support idInterpolatePattern and idStrategy
+        if let Some(descriptor_id) = descriptor.short_id.clone().or(descriptor.id.clone()) {
             if let Some(id_attr) = id_attr {
                 if let JSXAttrOrSpread::JSXAttr(attr) = id_attr {
-                    attr.to_owned().value = Some(JSXAttrValue::Lit(Lit::Str(Str::from(
-                        descriptor.id.unwrap(),
-                    ))));
+                    if is_id_overridden || descriptor.short_id.is_some() {
+                        attr.value = Some(create_jsx_str_attr_value(&descriptor_id));
+                    }
                 }
             } else if first_attr {
                 jsx_opening_elem.attrs.insert(
@@ -1092,9 +2798,7 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
                     JSXAttrOrSpread::JSXAttr(JSXAttr {
                         span: DUMMY_SP,
                         name: JSXAttrName::Ident(Ident::new("id".into(), DUMMY_SP)),
-                        value: Some(JSXAttrValue::Lit(Lit::Str(Str::from(
-                            descriptor.id.unwrap(),
-                        )))),
+                        value: Some(create_jsx_str_attr_value(&descriptor_id)),
                     }),
                 )
             }
@@ -1104,7 +2808,10 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
         for attr in jsx_opening_elem.attrs.drain(..) {
             match attr {
                 JSXAttrOrSpread::JSXAttr(attr) => {
-                    let key = get_message_descriptor_key_from_jsx(&attr.name);
+                    let key = get_message_descriptor_key_from_jsx(
+                        &attr.name,
+                        &self.options.descriptor_keys,
+                    );
                     match key {
                         "description" => {
                             // remove description
@@ -1115,27 +2822,39 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
                             }
                         }
                         "defaultMessage" => {
-                            if self.options.remove_default_message {
+                            if self.options.remove_default_message || self.options.id_only.is_some()
+                            {
                                 // remove defaultMessage
                             } else {
-                                /*
-                                if (ast && descriptor.defaultMessage) {
-                                    defaultMessageAttr
-                                        .get('value')
-                                        .replaceWith(t.jsxExpressionContainer(t.nullLiteral()))
-                                    const valueAttr = defaultMessageAttr.get(
-                                        'value'
-                                    ) as NodePath<t.JSXExpressionContainer>
-                                    valueAttr
-                                        .get('expression')
-                                        .replaceWithSourceString(
-                                        JSON.stringify(parse(descriptor.defaultMessage))
-                                        )
+                                let mut attr = attr;
+                                if self.translations.is_some() {
+                                    if let Some(message) = &inlined_message {
+                                        attr.value = Some(create_jsx_str_attr_value(message));
+                                    }
+                                }
+                                if self.options.ast {
+                                    if let Some(ast) =
+                                        inlined_message.as_deref().and_then(|message| {
+                                            create_ast_expr(message, &self.options.parser_options)
+                                        })
+                                    {
+                                        attr.value = Some(JSXAttrValue::JSXExprContainer(
+                                            JSXExprContainer {
+                                                span: DUMMY_SP,
+                                                expr: JSXExpr::Expr(Box::new(ast)),
+                                            },
+                                        ));
                                     }
-                                 */
+                                }
                                 attrs.push(JSXAttrOrSpread::JSXAttr(attr))
                             }
                         }
+                        key if self.options.descriptor_keys.remove_extra_fields
+                            && descriptor.extra.contains_key(key) =>
+                        {
+                            // remove extracted extra field
+                            self.comments.take_leading(attr.span.lo);
+                        }
                         _ => attrs.push(JSXAttrOrSpread::JSXAttr(attr)),
                     }
                 }
@@ -1149,25 +2868,49 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
     }
 
     fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        let span = call_expr.span;
         let callee = &call_expr.callee;
         let args = &mut call_expr.args;
 
-        if let Callee::Expr(callee_expr) = callee {
-            if let Expr::Ident(ident) = &**callee_expr {
-                if &*ident.sym == "defineMessage" || &*ident.sym == "defineMessages" {
-                    let first_arg = args.get_mut(0);
-                    let mut message_obj = get_message_object_from_expression(first_arg);
-
-                    assert_object_expression(&message_obj, callee);
-
-                    if &*ident.sym == "defineMessage" {
-                        self.process_message_object(&mut message_obj);
-                    } else if let Some(obj) = message_obj {
-                        if let Expr::Object(obj) = obj {
-                            for prop in obj.props.iter_mut() {
-                                if let PropOrSpread::Prop(prop) = &mut *prop {
-                                    if let Prop::KeyValue(kv) = &mut **prop {
-                                        self.process_message_object(&mut Some(&mut *kv.value));
+        if let Some(name) = self.get_define_messages_name(callee) {
+            let is_define_message = name == "defineMessage";
+            let first_arg = args.get_mut(0);
+            let mut message_obj = get_message_object_from_expression(first_arg);
+
+            assert_object_expression(&message_obj, callee, self.options.on_error.descriptor);
+
+            let is_bare_id = self
+                .options
+                .id_only
+                .as_ref()
+                .is_some_and(|id_only| id_only.bare_id);
+
+            if is_define_message {
+                self.process_message_object(&mut message_obj, None, span);
+                if let Some(message_obj) = message_obj.filter(|_| is_bare_id) {
+                    if let Some(id) =
+                        get_id_from_id_only_descriptor(message_obj, &self.options.descriptor_keys)
+                    {
+                        *message_obj = Expr::Lit(Lit::Str(Str::from(id)));
+                    }
+                }
+            } else if let Some(obj) = message_obj {
+                if let Expr::Object(obj) = obj {
+                    for prop in obj.props.iter_mut() {
+                        if let PropOrSpread::Prop(prop) = &mut *prop {
+                            if let Prop::KeyValue(kv) = &mut **prop {
+                                let key = get_static_prop_name(&kv.key).map(|key| key.to_string());
+                                self.process_message_object(
+                                    &mut Some(&mut *kv.value),
+                                    key.as_deref(),
+                                    kv.key.span(),
+                                );
+                                if is_bare_id {
+                                    if let Some(id) = get_id_from_id_only_descriptor(
+                                        &kv.value,
+                                        &self.options.descriptor_keys,
+                                    ) {
+                                        *kv.value = Expr::Lit(Lit::Str(Str::from(id)));
                                     }
                                 }
                             }
@@ -1179,27 +2922,102 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
 
         // Check that this is `intl.formatMessage` call
         if let Callee::Expr(expr) = &callee {
-            let is_format_message_call = match &**expr {
-                Expr::Ident(ident) if self.function_names.contains(&*ident.sym) => true,
-                Expr::Member(member_expr) => {
-                    if let MemberProp::Ident(ident) = &member_expr.prop {
-                        self.function_names.contains(&*ident.sym)
-                    } else {
-                        false
-                    }
-                }
-                _ => false,
-            };
+            if self.is_format_message_reference(expr) {
+                self.process_format_message_args(args, span);
+            }
+        }
 
-            if is_format_message_call {
-                let message_descriptor = args.get_mut(0);
-                if let Some(message_descriptor) = message_descriptor {
-                    if message_descriptor.expr.is_object() {
-                        self.process_message_object(&mut Some(message_descriptor.expr.as_mut()));
-                    }
-                }
+        call_expr.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        expr.visit_mut_children_with(self);
+
+        if let Expr::TaggedTpl(tagged_tpl) = expr {
+            if let Some(call_expr) = self.rewrite_tagged_template(tagged_tpl) {
+                *expr = call_expr;
+            }
+        }
+
+        if self.options.id_only.is_none() {
+            return;
+        }
+
+        // Replace `defineMessages({...})` with its argument, so unused messages can be
+        // tree-shaken. Calls which cannot be replaced are marked as pure instead.
+        if let Expr::Call(call_expr) = expr {
+            if self.get_define_messages_name(&call_expr.callee).is_none() {
+                return;
+            }
+
+            if call_expr.args.len() == 1 && call_expr.args[0].spread.is_none() {
+                *expr = *call_expr.args.pop().expect("Should have an argument").expr;
+            } else if !call_expr.span.is_dummy() {
+                self.comments.add_pure_comment(call_expr.span.lo);
+            }
+        }
+    }
+
+    fn visit_mut_opt_chain_expr(&mut self, opt_chain_expr: &mut OptChainExpr) {
+        // `intl.formatMessage?.(...)` or `formatMessage?.(...)`
+        if let OptChainBase::Call(opt_call) = &mut opt_chain_expr.base {
+            if self.is_format_message_reference(&opt_call.callee) {
+                self.process_format_message_args(&mut opt_call.args, opt_call.span);
+            }
+        }
+
+        opt_chain_expr.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
+        var_decl.visit_mut_children_with(self);
+
+        self.declare_var_decl(var_decl);
+    }
+
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        self.format_message_scopes.push();
+        for param in &function.params {
+            self.declare_pat(&param.pat);
+        }
+        function.visit_mut_children_with(self);
+        self.format_message_scopes.pop();
+    }
+
+    fn visit_mut_constructor(&mut self, constructor: &mut Constructor) {
+        self.format_message_scopes.push();
+        for param in &constructor.params {
+            if let ParamOrTsParamProp::Param(param) = param {
+                self.declare_pat(&param.pat);
             }
         }
+        constructor.visit_mut_children_with(self);
+        self.format_message_scopes.pop();
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow_expr: &mut ArrowExpr) {
+        self.format_message_scopes.push();
+        for param in &arrow_expr.params {
+            self.declare_pat(param);
+        }
+        arrow_expr.visit_mut_children_with(self);
+        self.format_message_scopes.pop();
+    }
+
+    fn visit_mut_catch_clause(&mut self, catch_clause: &mut CatchClause) {
+        self.format_message_scopes.push();
+        if let Some(param) = &catch_clause.param {
+            self.declare_pat(param);
+        }
+        catch_clause.visit_mut_children_with(self);
+        self.format_message_scopes.pop();
+    }
+
+    fn visit_mut_block_stmt(&mut self, block_stmt: &mut BlockStmt) {
+        self.format_message_scopes.push();
+        self.declare_hoisted(block_stmt.stmts.iter());
+        block_stmt.visit_mut_children_with(self);
+        self.format_message_scopes.pop();
     }
 
     fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
@@ -1209,15 +3027,89 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
         }
         */
 
-        for item in items {
-            self.read_pragma(item.span().lo, item.span().hi);
+        self.bindings = ConstBindings::collect(items);
+        self.imports = ImportResolver::collect(
+            items,
+            &self.module_source_names,
+            self.options.match_unimported_names,
+        );
+        self.format_message_scopes = Default::default();
+        self.format_message_scopes.push();
+        self.declare_hoisted(items.iter().filter_map(|item| match item {
+            ModuleItem::Stmt(stmt) => Some(stmt),
+            _ => None,
+        }));
+        for item in items.iter() {
+            if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item {
+                self.declare_decl(&export_decl.decl);
+            }
+        }
+        self.formatted_message_import = None;
+        let comments = collect_comments(items, &self.comments);
+        self.directives = FileDirectives::collect(&comments, &*self.source_map);
+        self.apply_option_overrides();
+        self.read_pragma(&comments);
+
+        for e in std::mem::take(&mut self.config_errors) {
+            emit_diagnostic(
+                self.options.on_error.config,
+                None,
+                &format!("[React Intl] {}", e),
+            );
+        }
+
+        for item in items.iter_mut() {
             item.visit_mut_children_with(self);
         }
 
+        if let Some(local) = self.formatted_message_import.take() {
+            // Keep directives like `"use client"` first.
+            let index = items
+                .iter()
+                .take_while(|item| {
+                    matches!(
+                        item,
+                        ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))
+                            if matches!(&**expr, Expr::Lit(Lit::Str(..)))
+                    )
+                })
+                .count();
+            items.insert(
+                index,
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: DUMMY_SP,
+                        local: Ident::new(local.as_str().into(), DUMMY_SP),
+                        imported: if local == "FormattedMessage" {
+                            None
+                        } else {
+                            Some(ModuleExportName::Ident(Ident::new(
+                                "FormattedMessage".into(),
+                                DUMMY_SP,
+                            )))
+                        },
+                        is_type_only: false,
+                    })],
+                    src: Str::from(DEFAULT_MODULE_SOURCE_NAMES[0]),
+                    type_only: false,
+                    asserts: None,
+                })),
+            );
+        }
+
         if self.options.__debug_extracted_messages_comment {
             let messages_json_str =
                 serde_json::to_string(&self.messages).expect("Should be serializable");
             let meta_json_str = serde_json::to_string(&self.meta).expect("Should be serializable");
+            let hardcoded_strings_json_str = if self.options.hardcoded_strings.is_some() {
+                format!(
+                    ", \"hardcodedStrings\":{}",
+                    serde_json::to_string(&self.hardcoded_strings).expect("Should be serializable")
+                )
+            } else {
+                String::new()
+            };
 
             // Append extracted messages to the end of the file as stringified JSON comments.
             // SWC's plugin does not support to return aribitary data other than transformed codes,
@@ -1229,8 +3121,8 @@ impl<C: Clone + Comments, S: SourceMapper> VisitMut for FormatJSVisitor<C, S> {
                     kind: CommentKind::Block,
                     span: Span::dummy_with_cmt(),
                     text: format!(
-                        "__formatjs__messages_extracted__::{{\"messages\":{}, \"meta\":{}}}",
-                        messages_json_str, meta_json_str
+                        "__formatjs__messages_extracted__::{{\"messages\":{}, \"meta\":{}{}}}",
+                        messages_json_str, meta_json_str, hardcoded_strings_json_str
                     )
                     .into(),
                 },
@@ -0,0 +1,150 @@
+use serde_json::Value;
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::ast::{
+        ArrayLit, Bool, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, Null, Number, ObjectLit,
+        Prop, PropName, PropOrSpread, Str,
+    },
+};
+
+pub fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn create_str(value: &str) -> Str {
+    Str {
+        span: DUMMY_SP,
+        value: value.into(),
+        raw: None,
+    }
+}
+
+/// Build the literal expression of a JSON value, i.e. to inline the pre-parsed
+/// message AST as an array literal instead of a JSON string.
+pub fn json_value_to_expr(value: &Value) -> Expr {
+    match value {
+        Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: *value,
+        })),
+        Value::Number(value) => Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: value.as_f64().unwrap_or_default(),
+            raw: None,
+        })),
+        Value::String(value) => Expr::Lit(Lit::Str(create_str(value))),
+        Value::Array(values) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: values
+                .iter()
+                .map(|value| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(json_value_to_expr(value)),
+                    })
+                })
+                .collect(),
+        }),
+        Value::Object(values) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: values
+                .iter()
+                .map(|(key, value)| {
+                    let key = if is_identifier_name(key) {
+                        PropName::Ident(Ident::new(key.as_str().into(), DUMMY_SP))
+                    } else {
+                        PropName::Str(create_str(key))
+                    };
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key,
+                        value: Box::new(json_value_to_expr(value)),
+                    })))
+                })
+                .collect(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn get_props(expr: &Expr) -> Vec<(&PropName, &Expr)> {
+        let obj = expr.as_object().expect("Should be an object");
+        obj.props
+            .iter()
+            .map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(KeyValueProp { key, value }) => (key, &**value),
+                    _ => panic!("Should be a key value prop"),
+                },
+                _ => panic!("Should not be a spread"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identifier_names() {
+        assert!(is_identifier_name("pluralType"));
+        assert!(is_identifier_name("_$a1"));
+        assert!(!is_identifier_name("1a"));
+        assert!(!is_identifier_name("data-id"));
+        assert!(!is_identifier_name(""));
+    }
+
+    #[test]
+    fn json_values() {
+        let expr = json_value_to_expr(&json!({
+            "type": 0,
+            "value": "Hello",
+            "data-offset": 1.5,
+            "options": [true, null, []],
+        }));
+        let props = get_props(&expr);
+
+        let keys: Vec<_> = props
+            .iter()
+            .map(|(key, _)| match key {
+                PropName::Ident(ident) => format!("ident {}", ident.sym),
+                PropName::Str(str) => format!("str {}", str.value),
+                _ => panic!("Should be an ident or a string"),
+            })
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "ident type",
+                "ident value",
+                "str data-offset",
+                "ident options"
+            ]
+        );
+
+        assert!(matches!(props[0].1, Expr::Lit(Lit::Num(Number { value, .. })) if *value == 0.0));
+        assert!(
+            matches!(props[1].1, Expr::Lit(Lit::Str(Str { value, .. })) if &**value == "Hello")
+        );
+        assert!(matches!(props[2].1, Expr::Lit(Lit::Num(Number { value, .. })) if *value == 1.5));
+
+        let elems: Vec<_> = props[3]
+            .1
+            .as_array()
+            .expect("Should be an array")
+            .elems
+            .iter()
+            .map(|elem| &*elem.as_ref().expect("Should not be a hole").expr)
+            .collect();
+        assert!(matches!(
+            elems[0],
+            Expr::Lit(Lit::Bool(Bool { value: true, .. }))
+        ));
+        assert!(matches!(elems[1], Expr::Lit(Lit::Null(..))));
+        assert!(matches!(elems[2], Expr::Array(ArrayLit { elems, .. }) if elems.is_empty()));
+    }
+}
@@ -0,0 +1,355 @@
+use swc_core::{
+    common::{BytePos, Span, Spanned},
+    ecma::ast::{BinExpr, BinaryOp, Expr, JSXAttrValue, JSXExpr, Lit, Str},
+};
+
+use crate::{
+    entities::{decode_jsx_entities_with, get_jsx_str_value},
+    evaluate::ConstBindings,
+};
+
+/// Maximum depth of `const` references followed while mapping an expression.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+/// Read `len` hex digits of an escape sequence.
+fn take_hex(chars: &mut std::iter::Peekable<std::str::CharIndices>, len: usize) -> Option<u32> {
+    let mut value = 0;
+    for _ in 0..len {
+        let (_, c) = chars.next()?;
+        value = value * 16 + c.to_digit(16)?;
+    }
+    Some(value)
+}
+
+/// Decode the escape sequences of the raw source of a string literal or template quasi
+/// starting at `lo`, pushing each UTF-16 code unit of the cooked value along with the
+/// span of the source text it was decoded from.
+fn decode_raw(raw: &str, lo: BytePos, units: &mut Vec<u16>, spans: &mut Vec<Span>) -> Option<()> {
+    let mut chars = raw.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let value = if c != '\\' {
+            Some(c as u32)
+        } else {
+            match chars.next()?.1 {
+                'n' => Some('\n' as u32),
+                't' => Some('\t' as u32),
+                'r' => Some('\r' as u32),
+                'b' => Some('\u{8}' as u32),
+                'f' => Some('\u{c}' as u32),
+                'v' => Some('\u{b}' as u32),
+                '0' => Some(0),
+                'x' => Some(take_hex(&mut chars, 2)?),
+                'u' => {
+                    if chars.next_if(|(_, c)| *c == '{').is_some() {
+                        let mut value = 0u32;
+                        loop {
+                            let (_, c) = chars.next()?;
+                            if c == '}' {
+                                break;
+                            }
+                            value = value.checked_mul(16)?.checked_add(c.to_digit(16)?)?;
+                        }
+                        Some(value)
+                    } else {
+                        Some(take_hex(&mut chars, 4)?)
+                    }
+                }
+                // Line continuations do not produce any characters.
+                '\r' => {
+                    chars.next_if(|(_, c)| *c == '\n');
+                    None
+                }
+                '\n' | '\u{2028}' | '\u{2029}' => None,
+                c => Some(c as u32),
+            }
+        };
+
+        let end = chars.peek().map_or(raw.len(), |(idx, _)| *idx);
+        let span = Span::new(
+            lo + BytePos(start as u32),
+            lo + BytePos(end as u32),
+            Default::default(),
+        );
+        match value {
+            // `\uXXXX` escapes can be halves of a surrogate pair.
+            Some(value) if value <= 0xffff => {
+                units.push(value as u16);
+                spans.push(span);
+            }
+            Some(value) => {
+                let mut buf = [0; 2];
+                for unit in char::from_u32(value)?.encode_utf16(&mut buf) {
+                    units.push(*unit);
+                    spans.push(span);
+                }
+            }
+            None => {}
+        }
+    }
+    Some(())
+}
+
+/// Source spans of each UTF-16 code unit of a statically evaluated message, so the
+/// locations of ICU syntax errors (which are UTF-16 offsets into the message) can be
+/// pointed at in the source file.
+#[derive(Debug, Clone, Default)]
+pub struct MessageSourceMap {
+    units: Vec<u16>,
+    spans: Vec<Span>,
+}
+
+impl MessageSourceMap {
+    pub fn from_expr(expr: &Expr, bindings: &ConstBindings) -> Option<Self> {
+        let mut map = MessageSourceMap::default();
+        map.push_expr(expr, bindings, 0)?;
+        Some(map)
+    }
+
+    pub fn from_jsx_attr_value(value: &JSXAttrValue, bindings: &ConstBindings) -> Option<Self> {
+        match value {
+            JSXAttrValue::Lit(Lit::Str(str)) => {
+                // JSX attribute strings do not have escape sequences, but entities.
+                let mut map = MessageSourceMap::default();
+                map.push_str_lit(str, false);
+                Some(map)
+            }
+            JSXAttrValue::JSXExprContainer(container) => match &container.expr {
+                JSXExpr::Expr(expr) => Self::from_expr(expr, bindings),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Map all units of the value to a single span.
+    fn push_value(&mut self, value: &str, span: Span) {
+        for unit in value.encode_utf16() {
+            self.units.push(unit);
+            self.spans.push(span);
+        }
+    }
+
+    /// Map the units of `value` decoded from `raw`, falling back to mapping the whole
+    /// value to `span` if the raw source does not decode to the value.
+    fn push_raw(&mut self, raw: &str, lo: BytePos, escapes: bool, value: &str, span: Span) {
+        let start = self.units.len();
+        let decoded = if escapes {
+            decode_raw(raw, lo, &mut self.units, &mut self.spans)
+        } else {
+            let mut buf = [0; 2];
+            decode_jsx_entities_with(raw, |c, range| {
+                let span = Span::new(
+                    lo + BytePos(range.start as u32),
+                    lo + BytePos(range.end as u32),
+                    Default::default(),
+                );
+                for unit in c.encode_utf16(&mut buf) {
+                    self.units.push(*unit);
+                    self.spans.push(span);
+                }
+            });
+            Some(())
+        };
+
+        if decoded.is_none() || !self.units[start..].iter().copied().eq(value.encode_utf16()) {
+            self.units.truncate(start);
+            self.spans.truncate(start);
+            self.push_value(value, span);
+        }
+    }
+
+    /// Map a string literal, either of JavaScript with `escapes` or of a JSX attribute.
+    fn push_str_lit(&mut self, str: &Str, escapes: bool) {
+        let value = if escapes {
+            str.value.to_string()
+        } else {
+            get_jsx_str_value(str)
+        };
+
+        match str.raw.as_deref() {
+            Some(raw) if raw.len() >= 2 => self.push_raw(
+                &raw[1..raw.len() - 1],
+                str.span.lo + BytePos(1),
+                escapes,
+                &value,
+                str.span,
+            ),
+            _ => self.push_value(&value, str.span),
+        }
+    }
+
+    fn push_expr(&mut self, expr: &Expr, bindings: &ConstBindings, depth: usize) -> Option<()> {
+        if depth > MAX_REFERENCE_DEPTH {
+            return None;
+        }
+
+        match expr {
+            Expr::Lit(Lit::Str(str)) => self.push_str_lit(str, true),
+            Expr::Tpl(tpl) => {
+                for (idx, quasi) in tpl.quasis.iter().enumerate() {
+                    let cooked = quasi.cooked.as_ref()?;
+                    self.push_raw(&quasi.raw, quasi.span.lo, true, cooked, quasi.span);
+                    if let Some(expr) = tpl.exprs.get(idx) {
+                        self.push_expr(expr, bindings, depth)?;
+                    }
+                }
+            }
+            Expr::Bin(BinExpr {
+                op: BinaryOp::Add,
+                left,
+                right,
+                ..
+            }) => {
+                let start = self.units.len();
+                self.push_expr(left, bindings, depth)?;
+                self.push_expr(right, bindings, depth)?;
+
+                // Numbers are added rather than concatenated.
+                let value = bindings.evaluate_string(expr)?;
+                if !self.units[start..].iter().copied().eq(value.encode_utf16()) {
+                    self.units.truncate(start);
+                    self.spans.truncate(start);
+                    self.push_value(&value, expr.span());
+                }
+            }
+            Expr::Paren(paren) => self.push_expr(&paren.expr, bindings, depth)?,
+            Expr::TsAs(ts_as) => self.push_expr(&ts_as.expr, bindings, depth)?,
+            Expr::TsConstAssertion(assertion) => {
+                self.push_expr(&assertion.expr, bindings, depth)?
+            }
+            Expr::TsTypeAssertion(assertion) => self.push_expr(&assertion.expr, bindings, depth)?,
+            Expr::TsNonNull(non_null) => self.push_expr(&non_null.expr, bindings, depth)?,
+            Expr::Ident(ident) => {
+                let init = bindings.get_const(&ident.sym)?;
+                self.push_expr(init, bindings, depth + 1)?;
+            }
+            _ => {
+                let value = bindings.evaluate_string(expr)?;
+                self.push_value(&value, expr.span());
+            }
+        }
+
+        Some(())
+    }
+
+    /// Apply the whitespace normalization messages go through unless `preserveWhitespace`
+    /// is set, collapsing runs of whitespace into a single space and trimming the ends.
+    pub fn collapse_whitespace(self) -> Option<Self> {
+        let mut map = MessageSourceMap::default();
+        let mut idx = 0;
+        let mut in_whitespace = false;
+        let mut buf = [0; 2];
+
+        for c in char::decode_utf16(self.units.iter().copied()) {
+            let c = c.ok()?;
+            let len = c.len_utf16();
+            if c.is_whitespace() {
+                if !in_whitespace {
+                    map.units.push(' ' as u16);
+                    map.spans.push(self.spans[idx]);
+                }
+                in_whitespace = true;
+            } else {
+                for (offset, unit) in c.encode_utf16(&mut buf).iter().enumerate() {
+                    map.units.push(*unit);
+                    map.spans.push(self.spans[idx + offset]);
+                }
+                in_whitespace = false;
+            }
+            idx += len;
+        }
+
+        let start = map
+            .units
+            .iter()
+            .take_while(|unit| **unit == ' ' as u16)
+            .count();
+        let end = map.units.len()
+            - map.units[start..]
+                .iter()
+                .rev()
+                .take_while(|unit| **unit == ' ' as u16)
+                .count();
+        Some(MessageSourceMap {
+            units: map.units[start..end].to_vec(),
+            spans: map.spans[start..end].to_vec(),
+        })
+    }
+
+    /// Returns the source span of the UTF-16 range `start..end` of the message, if the map
+    /// was built from the same message.
+    pub fn get_span(&self, message: &str, start: usize, end: usize) -> Option<Span> {
+        if !self.units.iter().copied().eq(message.encode_utf16()) {
+            return None;
+        }
+
+        let last = self.spans.last()?;
+        if start >= self.spans.len() {
+            return Some(Span::new(last.hi, last.hi, Default::default()));
+        }
+
+        let lo = self.spans[start];
+        let hi = self.spans[end.clamp(start + 1, self.spans.len()) - 1];
+        Some(Span::new(lo.lo, hi.hi.max(lo.hi), Default::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn decode(raw: &str) -> (String, Vec<(u32, u32)>) {
+        let mut units = vec![];
+        let mut spans = vec![];
+        decode_raw(raw, BytePos(10), &mut units, &mut spans).unwrap();
+        (
+            String::from_utf16(&units).unwrap(),
+            spans.iter().map(|span| (span.lo.0, span.hi.0)).collect(),
+        )
+    }
+
+    #[test]
+    fn escaped_units() {
+        assert_eq!(
+            decode(r"a\n\x41"),
+            ("a\nA".to_string(), vec![(10, 11), (11, 13), (13, 17)])
+        );
+        assert_eq!(
+            decode("\\u{1F600}\\\n{"),
+            ("😀{".to_string(), vec![(10, 19), (10, 19), (21, 22)])
+        );
+        assert_eq!(
+            decode(r"😀é"),
+            ("😀é".to_string(), vec![(10, 14), (10, 14), (14, 16)])
+        );
+    }
+
+    #[test]
+    fn collapsed_whitespace() {
+        let mut map = MessageSourceMap::default();
+        map.push_raw(
+            "  a \t b ",
+            BytePos(0),
+            true,
+            "  a \t b ",
+            Default::default(),
+        );
+        let map = map.collapse_whitespace().unwrap();
+
+        assert_eq!(
+            map.get_span("a b", 2, 3),
+            Some(Span::new(BytePos(6), BytePos(7), Default::default()))
+        );
+        assert_eq!(
+            map.get_span("a b", 1, 1),
+            Some(Span::new(BytePos(3), BytePos(4), Default::default()))
+        );
+        assert_eq!(
+            map.get_span("a b", 3, 3),
+            Some(Span::new(BytePos(7), BytePos(7), Default::default()))
+        );
+        assert_eq!(map.get_span("a  b", 0, 1), None);
+    }
+}
@@ -0,0 +1,253 @@
+use swc_core::common::{comments::Comment, BytePos, Span};
+
+/// Entries of a pragma comment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pragma {
+    pub entries: Vec<(String, String)>,
+    pub errors: Vec<PragmaError>,
+}
+
+/// A malformed entry of a pragma comment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PragmaError {
+    pub message: String,
+    pub span: Span,
+}
+
+/// Parse the `key:value` entries following `pragma` in the comment, i.e.
+/// `// @intl-meta project:checkout owner:"Team Payments"`. Values can be quoted with
+/// `"` or `'` to contain whitespace, with `\` escaping the quote. Returns `None` if the
+/// comment does not contain the pragma.
+pub fn parse_pragma(comment: &Comment, pragma: &str) -> Option<Pragma> {
+    if pragma.is_empty() {
+        return None;
+    }
+
+    let text = &*comment.text;
+    let start = text.match_indices(pragma).find_map(|(index, _)| {
+        let end = index + pragma.len();
+        text[end..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+            .then_some(end)
+    })?;
+
+    let mut scanner = Scanner {
+        text,
+        pos: start,
+        // The text of both line and block comments starts after `//` or `/*`.
+        base: comment.span.lo + BytePos(2),
+    };
+    let mut parsed = Pragma::default();
+
+    while scanner.skip_whitespace() {
+        match scanner.parse_entry() {
+            Ok(entry) => parsed.entries.push(entry),
+            Err(error) => {
+                parsed.errors.push(error);
+                scanner.skip_token();
+            }
+        }
+    }
+
+    Some(parsed)
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+    base: BytePos,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn span(&self, start: usize) -> Span {
+        Span::new(
+            self.base + BytePos(start as u32),
+            self.base + BytePos(self.pos as u32),
+            Default::default(),
+        )
+    }
+
+    /// Skip whitespace, as well as the `*` decorating lines of block comments. Returns
+    /// whether there is anything left.
+    fn skip_whitespace(&mut self) -> bool {
+        let mut line_start = false;
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                line_start = true;
+            } else if !(c.is_whitespace() || (line_start && c == '*')) {
+                break;
+            }
+            self.bump();
+        }
+        self.peek().is_some()
+    }
+
+    fn skip_token(&mut self) {
+        while self.peek().is_some_and(|c| !c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn parse_entry(&mut self) -> Result<(String, String), PragmaError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c != ':' && !c.is_whitespace()) {
+            self.bump();
+        }
+        let key = self.text[start..self.pos].to_string();
+
+        if self.peek() != Some(':') {
+            return Err(PragmaError {
+                message: format!("Expected `key:value`, found `{}`", key),
+                span: self.span(start),
+            });
+        }
+        if key.is_empty() {
+            self.bump();
+            return Err(PragmaError {
+                message: "Missing key before `:`".to_string(),
+                span: self.span(start),
+            });
+        }
+        self.bump();
+
+        let value = match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                let value_start = self.pos;
+                self.bump();
+                let mut value = String::new();
+                loop {
+                    match self.bump() {
+                        Some('\\') => match self.bump() {
+                            Some(c) => value.push(c),
+                            None => break,
+                        },
+                        Some(c) if c == quote => {
+                            if self.peek().is_some_and(|c| !c.is_whitespace()) {
+                                self.skip_token();
+                                return Err(PragmaError {
+                                    message: format!(
+                                        "Expected whitespace after the quoted value of `{}`",
+                                        key
+                                    ),
+                                    span: self.span(value_start),
+                                });
+                            }
+                            return Ok((key, value));
+                        }
+                        Some(c) => value.push(c),
+                        None => break,
+                    }
+                }
+                return Err(PragmaError {
+                    message: format!("Unterminated quoted value of `{}`", key),
+                    span: self.span(value_start),
+                });
+            }
+            _ => {
+                let value_start = self.pos;
+                self.skip_token();
+                self.text[value_start..self.pos].to_string()
+            }
+        };
+
+        Ok((key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::common::comments::CommentKind;
+
+    use super::*;
+
+    type Parsed = (Vec<(String, String)>, Vec<(String, u32, u32)>);
+
+    fn parse(text: &str) -> Option<Parsed> {
+        let comment = Comment {
+            kind: CommentKind::Line,
+            span: Span::new(
+                BytePos(10),
+                BytePos(12 + text.len() as u32),
+                Default::default(),
+            ),
+            text: text.into(),
+        };
+        parse_pragma(&comment, "@intl-meta").map(|parsed| {
+            (
+                parsed.entries,
+                parsed
+                    .errors
+                    .into_iter()
+                    .map(|e| (e.message, e.span.lo.0, e.span.hi.0))
+                    .collect(),
+            )
+        })
+    }
+
+    fn entries(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn entries_with_quoted_values() {
+        assert_eq!(
+            parse(r#" @intl-meta project:foo pattern:[sha1:hash] owner:"Team \"P\"" x:'a b'"#),
+            Some((
+                entries(&[
+                    ("project", "foo"),
+                    ("pattern", "[sha1:hash]"),
+                    ("owner", "Team \"P\""),
+                    ("x", "a b"),
+                ]),
+                vec![]
+            ))
+        );
+        assert_eq!(
+            parse("*\n * @intl-meta project:foo\n * team:core\n "),
+            Some((entries(&[("project", "foo"), ("team", "core")]), vec![]))
+        );
+    }
+
+    #[test]
+    fn without_pragma() {
+        assert_eq!(parse(" @intl-metadata project:foo"), None);
+        assert_eq!(parse(" project:foo"), None);
+        assert_eq!(parse(" @intl-meta"), Some((vec![], vec![])));
+    }
+
+    #[test]
+    fn malformed_entries() {
+        assert_eq!(
+            parse(r#" @intl-meta project :foo owner:"Team"x a:"b"#),
+            Some((
+                vec![],
+                vec![
+                    ("Expected `key:value`, found `project`".to_string(), 24, 31),
+                    ("Missing key before `:`".to_string(), 32, 33),
+                    (
+                        "Expected whitespace after the quoted value of `owner`".to_string(),
+                        43,
+                        50
+                    ),
+                    ("Unterminated quoted value of `a`".to_string(), 53, 55),
+                ]
+            ))
+        );
+    }
+}
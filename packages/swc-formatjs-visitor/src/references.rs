@@ -0,0 +1,251 @@
+use swc_core::ecma::ast::{
+    CallExpr, Callee, Expr, KeyValueProp, Lit, MemberExpr, MemberProp, ObjectLit, Prop,
+    PropOrSpread,
+};
+
+use crate::{aliases::get_static_prop_name, evaluate::ConstBindings};
+
+/// Maximum depth of references followed while resolving a descriptor, which also guards
+/// against cyclic references.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+/// Where a resolved descriptor is declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptorOrigin {
+    /// An argument of `defineMessages` / `defineMessage`, which is extracted and
+    /// transformed where it's declared.
+    DefineMessages,
+    /// A plain object literal.
+    Object,
+}
+
+/// A descriptor object resolved from a reference, with spreads of other objects of the
+/// module inlined.
+#[derive(Debug, Clone)]
+pub struct ResolvedDescriptor {
+    pub object: ObjectLit,
+    pub origin: DescriptorOrigin,
+}
+
+/// Resolves references to descriptors declared in the same module, i.e. `msgs.header` for
+/// `const msgs = defineMessages({header: {...}})`, or `base` in `{...base, defaultMessage}`.
+/// References are resolved through `const` bindings only.
+pub struct DescriptorResolver<'a, F: Fn(&Callee) -> bool> {
+    pub bindings: &'a ConstBindings,
+    /// Whether the callee is `defineMessages` or `defineMessage`.
+    pub is_define_messages: F,
+}
+
+impl<F: Fn(&Callee) -> bool> DescriptorResolver<'_, F> {
+    /// Resolve the expression to the object literal it refers to.
+    pub fn resolve(&self, expr: &Expr) -> Option<ResolvedDescriptor> {
+        self.resolve_object(expr, 0)
+    }
+
+    /// Inline the spreads of an object literal, or `None` if any of them cannot be
+    /// resolved. Properties override the ones preceding them, like they do at runtime.
+    pub fn flatten(&self, object: &ObjectLit) -> Option<ObjectLit> {
+        self.flatten_object(object, 0)
+    }
+
+    fn resolve_object(&self, expr: &Expr, depth: usize) -> Option<ResolvedDescriptor> {
+        if depth > MAX_REFERENCE_DEPTH {
+            return None;
+        }
+
+        match expr {
+            Expr::Object(object) => Some(ResolvedDescriptor {
+                object: self.flatten_object(object, depth)?,
+                origin: DescriptorOrigin::Object,
+            }),
+            Expr::Ident(ident) => {
+                self.resolve_object(self.bindings.get_const(&ident.sym)?, depth + 1)
+            }
+            Expr::Member(MemberExpr { obj, prop, .. }) => {
+                let name = match prop {
+                    MemberProp::Ident(ident) => &*ident.sym,
+                    MemberProp::Computed(computed) => match &*computed.expr {
+                        Expr::Lit(Lit::Str(str)) => &*str.value,
+                        _ => return None,
+                    },
+                    MemberProp::PrivateName(..) => return None,
+                };
+                let parent = self.resolve_object(obj, depth + 1)?;
+                let value = get_prop_value(&parent.object, name)?;
+                let resolved = self.resolve_object(&value, depth + 1)?;
+                Some(ResolvedDescriptor {
+                    origin: if parent.origin == DescriptorOrigin::DefineMessages {
+                        parent.origin
+                    } else {
+                        resolved.origin
+                    },
+                    ..resolved
+                })
+            }
+            Expr::Call(CallExpr { callee, args, .. })
+                if args.len() == 1
+                    && args[0].spread.is_none()
+                    && (self.is_define_messages)(callee) =>
+            {
+                Some(ResolvedDescriptor {
+                    origin: DescriptorOrigin::DefineMessages,
+                    ..self.resolve_object(&args[0].expr, depth + 1)?
+                })
+            }
+            Expr::Paren(paren) => self.resolve_object(&paren.expr, depth),
+            Expr::TsAs(ts_as) => self.resolve_object(&ts_as.expr, depth),
+            Expr::TsConstAssertion(assertion) => self.resolve_object(&assertion.expr, depth),
+            Expr::TsTypeAssertion(assertion) => self.resolve_object(&assertion.expr, depth),
+            Expr::TsNonNull(non_null) => self.resolve_object(&non_null.expr, depth),
+            _ => None,
+        }
+    }
+
+    fn flatten_object(&self, object: &ObjectLit, depth: usize) -> Option<ObjectLit> {
+        let mut props: Vec<PropOrSpread> = vec![];
+        for prop in &object.props {
+            match prop {
+                PropOrSpread::Spread(spread) => {
+                    let resolved = self.resolve_object(&spread.expr, depth + 1)?;
+                    for prop in resolved.object.props {
+                        push_prop(&mut props, prop);
+                    }
+                }
+                PropOrSpread::Prop(..) => push_prop(&mut props, prop.clone()),
+            }
+        }
+
+        Some(ObjectLit {
+            span: object.span,
+            props,
+        })
+    }
+}
+
+fn get_prop_key(prop: &PropOrSpread) -> Option<&str> {
+    match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::Shorthand(ident) => Some(&ident.sym),
+            Prop::KeyValue(KeyValueProp { key, .. }) => get_static_prop_name(key),
+            _ => None,
+        },
+        PropOrSpread::Spread(..) => None,
+    }
+}
+
+/// Push the property, replacing a preceding property of the same key.
+fn push_prop(props: &mut Vec<PropOrSpread>, prop: PropOrSpread) {
+    if let Some(key) = get_prop_key(&prop) {
+        props.retain(|existing| get_prop_key(existing) != Some(key));
+    }
+    props.push(prop);
+}
+
+/// Returns the value of the last property of the given key.
+fn get_prop_value(object: &ObjectLit, name: &str) -> Option<Expr> {
+    object.props.iter().rev().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, value })
+                if get_static_prop_name(key) == Some(name) =>
+            {
+                Some((**value).clone())
+            }
+            Prop::Shorthand(ident) if &*ident.sym == name => Some(Expr::Ident(ident.clone())),
+            _ => None,
+        },
+        PropOrSpread::Spread(..) => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::{
+        common::DUMMY_SP,
+        ecma::ast::{Ident, PropName, SpreadElement, Str},
+    };
+
+    use super::*;
+
+    fn prop(key: &str, value: &str) -> PropOrSpread {
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident::new(key.into(), DUMMY_SP)),
+            value: Box::new(Expr::Lit(Lit::Str(Str::from(value)))),
+        })))
+    }
+
+    fn spread(props: Vec<PropOrSpread>) -> PropOrSpread {
+        PropOrSpread::Spread(SpreadElement {
+            dot3_token: DUMMY_SP,
+            expr: Box::new(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props,
+            })),
+        })
+    }
+
+    #[test]
+    fn flatten_overrides_preceding_props() {
+        let bindings = ConstBindings::default();
+        let resolver = DescriptorResolver {
+            bindings: &bindings,
+            is_define_messages: |_: &Callee| false,
+        };
+        let object = ObjectLit {
+            span: DUMMY_SP,
+            props: vec![
+                prop("defaultMessage", "Hi"),
+                spread(vec![
+                    prop("id", "greeting"),
+                    prop("defaultMessage", "Hello"),
+                ]),
+                prop("id", "welcome"),
+            ],
+        };
+
+        let flattened = resolver.flatten(&object).unwrap();
+        let props: Vec<_> = flattened
+            .props
+            .iter()
+            .map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(KeyValueProp { key, value }) => match &**value {
+                        Expr::Lit(Lit::Str(str)) => (
+                            get_static_prop_name(key).unwrap().to_string(),
+                            str.value.to_string(),
+                        ),
+                        _ => unreachable!(),
+                    },
+                    _ => unreachable!(),
+                },
+                PropOrSpread::Spread(..) => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(
+            props,
+            vec![
+                ("defaultMessage".to_string(), "Hello".to_string()),
+                ("id".to_string(), "welcome".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_spread() {
+        let bindings = ConstBindings::default();
+        let resolver = DescriptorResolver {
+            bindings: &bindings,
+            is_define_messages: |_: &Callee| false,
+        };
+        let object = ObjectLit {
+            span: DUMMY_SP,
+            props: vec![PropOrSpread::Spread(SpreadElement {
+                dot3_token: DUMMY_SP,
+                expr: Box::new(Expr::Ident(Ident::new("unknown".into(), DUMMY_SP))),
+            })],
+        };
+
+        assert!(resolver.flatten(&object).is_none());
+    }
+}
@@ -0,0 +1,179 @@
+use swc_core::{
+    common::{EqIgnoreSpan, Span, DUMMY_SP},
+    ecma::ast::{
+        Expr, Ident, KeyValueProp, MemberExpr, MemberProp, Number, ObjectLit, Prop, PropName,
+        PropOrSpread, Tpl,
+    },
+};
+
+/// The function tagged templates are rewritten to call by default.
+pub static DEFAULT_FORMAT_MESSAGE: &str = "intl.formatMessage";
+
+/// A placeholder of a tagged template message.
+#[derive(Debug, Clone, PartialEq)]
+enum Placeholder {
+    /// `{name}` for `${name}`.
+    Named(String),
+    /// `{0}` for any other expression, by its position in the template.
+    Positional(usize),
+}
+
+/// The ICU message of a tagged template, i.e. `You have {count} items` for
+/// `` t`You have ${count} items` ``.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateMessage {
+    pub message: String,
+    values: Vec<(Placeholder, Expr)>,
+}
+
+impl TemplateMessage {
+    /// Join the quasis of the template with placeholders of its expressions. The quasis
+    /// are ICU syntax, so they can contain plurals or selects of the placeholders. Returns
+    /// the span of a quasi with an invalid escape sequence.
+    pub fn from_tpl(tpl: &Tpl) -> Result<Self, Span> {
+        let mut message = TemplateMessage::default();
+
+        for (index, quasi) in tpl.quasis.iter().enumerate() {
+            let cooked = quasi.cooked.as_ref().ok_or(quasi.span)?;
+            message.message.push_str(cooked);
+
+            if let Some(expr) = tpl.exprs.get(index) {
+                let placeholder = match &**expr {
+                    Expr::Ident(ident) => Placeholder::Named(ident.sym.to_string()),
+                    _ => Placeholder::Positional(index),
+                };
+                match &placeholder {
+                    Placeholder::Named(name) => message.message.push_str(&format!("{{{}}}", name)),
+                    Placeholder::Positional(index) => {
+                        message.message.push_str(&format!("{{{}}}", index))
+                    }
+                }
+
+                let is_declared = message.values.iter().any(|(existing, value)| {
+                    *existing == placeholder && value.eq_ignore_span(expr)
+                });
+                if !is_declared {
+                    message.values.push((placeholder, (**expr).clone()));
+                }
+            }
+        }
+
+        Ok(message)
+    }
+
+    /// Object literal of the values, passed as the values of `formatMessage`. `None` if the
+    /// template has no expressions.
+    pub fn values_object(&self) -> Option<Expr> {
+        if self.values.is_empty() {
+            return None;
+        }
+
+        Some(Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: self
+                .values
+                .iter()
+                .map(|(placeholder, value)| {
+                    let prop = match (placeholder, value) {
+                        (Placeholder::Named(_), Expr::Ident(ident)) => {
+                            Prop::Shorthand(ident.clone())
+                        }
+                        (Placeholder::Named(name), _) => Prop::KeyValue(KeyValueProp {
+                            key: PropName::Ident(Ident::new(name.as_str().into(), DUMMY_SP)),
+                            value: Box::new(value.clone()),
+                        }),
+                        (Placeholder::Positional(index), _) => Prop::KeyValue(KeyValueProp {
+                            key: PropName::Num(Number {
+                                span: DUMMY_SP,
+                                value: *index as f64,
+                                raw: None,
+                            }),
+                            value: Box::new(value.clone()),
+                        }),
+                    };
+                    PropOrSpread::Prop(Box::new(prop))
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// Build the member expression of a path like `intl.formatMessage`.
+pub fn create_callee(path: &str) -> Expr {
+    let mut segments = path.split('.');
+    let mut callee = Expr::Ident(Ident::new(
+        segments.next().unwrap_or_default().into(),
+        DUMMY_SP,
+    ));
+    for segment in segments {
+        callee = Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(callee),
+            prop: MemberProp::Ident(Ident::new(segment.into(), DUMMY_SP)),
+        });
+    }
+    callee
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use swc_core::ecma::ast::{BinExpr, BinaryOp, TplElement};
+
+    use super::*;
+
+    fn tpl(quasis: &[&str], exprs: Vec<Expr>) -> Tpl {
+        Tpl {
+            span: DUMMY_SP,
+            exprs: exprs.into_iter().map(Box::new).collect(),
+            quasis: quasis
+                .iter()
+                .enumerate()
+                .map(|(index, quasi)| TplElement {
+                    span: DUMMY_SP,
+                    tail: index == quasis.len() - 1,
+                    cooked: Some((*quasi).into()),
+                    raw: (*quasi).into(),
+                })
+                .collect(),
+        }
+    }
+
+    fn ident(name: &str) -> Expr {
+        Expr::Ident(Ident::new(name.into(), DUMMY_SP))
+    }
+
+    #[test]
+    fn placeholders() {
+        let sum = Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: BinaryOp::Add,
+            left: Box::new(ident("a")),
+            right: Box::new(ident("b")),
+        });
+        let message = TemplateMessage::from_tpl(&tpl(
+            &["You have ", " items, ", " in total, ", " again"],
+            vec![ident("count"), sum, ident("count")],
+        ))
+        .unwrap();
+
+        assert_eq!(
+            message.message,
+            "You have {count} items, {1} in total, {count} again"
+        );
+        assert_eq!(message.values.len(), 2);
+        assert_eq!(message.values[1].0, Placeholder::Positional(1));
+    }
+
+    #[test]
+    fn callee_path() {
+        assert!(
+            create_callee("intl.formatMessage").eq_ignore_span(&Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(ident("intl")),
+                prop: MemberProp::Ident(Ident::new("formatMessage".into(), DUMMY_SP)),
+            }))
+        );
+        assert!(create_callee("formatMessage").eq_ignore_span(&ident("formatMessage")));
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use crate::{CatalogMessage, TranslationsOptions};
+
+/// Locales to look translations up from, in order: the locale itself, its parent locales
+/// (`zh-Hant-TW`, `zh-Hant`, `zh`) and then the configured fallback locales.
+fn get_locale_chain(locale: &str, fallback_locales: &[String]) -> Vec<String> {
+    let mut chain: Vec<String> = vec![];
+    let mut push = |locale: &str| {
+        if !locale.is_empty() && !chain.iter().any(|l| l == locale) {
+            chain.push(locale.to_string());
+        }
+    };
+
+    let mut locale = locale;
+    loop {
+        push(locale);
+        match locale.rfind(['-', '_']) {
+            Some(idx) => locale = &locale[..idx],
+            None => break,
+        }
+    }
+    for fallback_locale in fallback_locales {
+        push(fallback_locale);
+    }
+
+    chain
+}
+
+/// Translations catalogs of the locale messages are inlined for.
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    locales: Vec<String>,
+    catalogs: HashMap<String, HashMap<String, String>>,
+}
+
+impl Translations {
+    /// Load the embedded catalogs and the catalog files of the locale chain. Catalogs
+    /// which cannot be read are skipped and reported in the returned errors.
+    pub fn load(options: &TranslationsOptions) -> (Self, Vec<String>) {
+        let locales = get_locale_chain(&options.locale, &options.fallback_locales);
+        let mut catalogs: HashMap<String, HashMap<String, String>> = Default::default();
+        let mut errors = vec![];
+
+        for locale in &locales {
+            let mut catalog: HashMap<String, String> = Default::default();
+
+            if let Some(path) = options.catalog_files.get(locale) {
+                let messages = std::fs::read_to_string(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|content| {
+                        serde_json::from_str::<HashMap<String, CatalogMessage>>(&content)
+                            .map_err(|e| e.to_string())
+                    });
+                match messages {
+                    Ok(messages) => catalog.extend(
+                        messages
+                            .into_iter()
+                            .map(|(id, message)| (id, message.into_message())),
+                    ),
+                    Err(e) => errors.push(format!(
+                        "Cannot read translations of `{}` from `{}`: {}",
+                        locale, path, e
+                    )),
+                }
+            }
+
+            if let Some(messages) = options.catalogs.get(locale) {
+                catalog.extend(
+                    messages
+                        .iter()
+                        .map(|(id, message)| (id.clone(), message.clone().into_message())),
+                );
+            }
+
+            catalogs.insert(locale.clone(), catalog);
+        }
+
+        (Translations { locales, catalogs }, errors)
+    }
+
+    /// Returns the translation of a message with the locale it was found in.
+    pub fn get(&self, id: &str) -> Option<(&str, &str)> {
+        self.locales.iter().find_map(|locale| {
+            self.catalogs
+                .get(locale)
+                .and_then(|catalog| catalog.get(id))
+                .map(|message| (locale.as_str(), message.as_str()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn locale_chain() {
+        assert_eq!(
+            get_locale_chain("zh-Hant-TW", &["en".to_string(), "zh".to_string()]),
+            vec!["zh-Hant-TW", "zh-Hant", "zh", "en"]
+        );
+        assert_eq!(get_locale_chain("fr", &[]), vec!["fr"]);
+    }
+
+    #[test]
+    fn fallback_translations() {
+        let catalog = |messages: &[(&str, &str)]| {
+            messages
+                .iter()
+                .map(|(id, message)| (id.to_string(), CatalogMessage::Str(message.to_string())))
+                .collect::<HashMap<_, _>>()
+        };
+        let options = TranslationsOptions {
+            locale: "de-AT".to_string(),
+            fallback_locales: vec!["en".to_string()],
+            catalogs: [
+                ("de-AT".to_string(), catalog(&[("jan", "Jänner")])),
+                (
+                    "de".to_string(),
+                    catalog(&[("jan", "Januar"), ("feb", "Februar")]),
+                ),
+                ("en".to_string(), catalog(&[("mar", "March")])),
+            ]
+            .into_iter()
+            .collect(),
+            catalog_files: [("en".to_string(), "/does/not/exist.json".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+
+        let (translations, errors) = Translations::load(&options);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(translations.get("jan"), Some(("de-AT", "Jänner")));
+        assert_eq!(translations.get("feb"), Some(("de", "Februar")));
+        assert_eq!(translations.get("mar"), Some(("en", "March")));
+        assert_eq!(translations.get("apr"), None);
+    }
+}
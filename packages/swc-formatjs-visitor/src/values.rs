@@ -0,0 +1,219 @@
+use icu_messageformat_parser::Ast;
+use swc_core::{
+    common::{Span, Spanned},
+    ecma::ast::{Expr, ObjectLit, Prop, PropName, PropOrSpread},
+};
+
+use crate::{aliases::get_static_prop_name, consistency::get_placeholder_names};
+
+/// A mismatch between the placeholders of a message and the `values` passed with it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValuesIssue {
+    /// An argument or tag of the message without a value.
+    MissingValue { name: String },
+    /// A value which is not used by the message.
+    UnusedValue { name: String, span: Span },
+    /// The value of a rich text tag which is not a function.
+    TagValueNotFunction { name: String, span: Span },
+}
+
+impl ValuesIssue {
+    pub fn message(&self) -> String {
+        match self {
+            ValuesIssue::MissingValue { name } => format!(
+                "[React Intl] Missing value of `{}`, which is used by the message.",
+                name
+            ),
+            ValuesIssue::UnusedValue { name, .. } => {
+                format!("[React Intl] Value `{}` is not used by the message.", name)
+            }
+            ValuesIssue::TagValueNotFunction { name, .. } => format!(
+                "[React Intl] Value of the rich text tag `{}` must be a function.",
+                name
+            ),
+        }
+    }
+
+    /// Span of the offending value, if any.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ValuesIssue::MissingValue { .. } => None,
+            ValuesIssue::UnusedValue { span, .. }
+            | ValuesIssue::TagValueNotFunction { span, .. } => Some(*span),
+        }
+    }
+}
+
+/// Compare the placeholders of a message with the keys of the `values` object literal.
+/// Values with spreads or computed keys cannot be checked, so nothing is reported for them.
+/// Tags may be provided by react-intl's `defaultRichTextElements`, so tags without a value
+/// are only reported with the list of those elements, if they're not listed.
+pub fn check_values(
+    ast: &Ast,
+    values: &ObjectLit,
+    default_rich_text_elements: Option<&[String]>,
+) -> Vec<ValuesIssue> {
+    let (arguments, tags) = get_placeholder_names(ast);
+
+    let mut entries = vec![];
+    for prop in &values.props {
+        let entry = match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(ident) => Some((ident.sym.to_string(), prop.span(), None)),
+                Prop::KeyValue(kv) => {
+                    get_value_key(&kv.key).map(|key| (key, prop.span(), Some(&*kv.value)))
+                }
+                Prop::Method(method) => {
+                    get_value_key(&method.key).map(|key| (key, prop.span(), None))
+                }
+                _ => None,
+            },
+            PropOrSpread::Spread(..) => None,
+        };
+        match entry {
+            Some(entry) => entries.push(entry),
+            None => return vec![],
+        }
+    }
+
+    let mut issues = vec![];
+    let required_tags = tags
+        .iter()
+        .filter(|tag| default_rich_text_elements.is_some_and(|elements| !elements.contains(tag)));
+    for name in arguments.iter().chain(required_tags) {
+        if !entries.iter().any(|(key, ..)| key == name) {
+            issues.push(ValuesIssue::MissingValue { name: name.clone() });
+        }
+    }
+    for (key, span, value) in entries {
+        if !arguments.contains(&key) && !tags.contains(&key) {
+            issues.push(ValuesIssue::UnusedValue { name: key, span });
+        } else if tags.contains(&key) && value.is_some_and(is_never_function) {
+            issues.push(ValuesIssue::TagValueNotFunction { name: key, span });
+        }
+    }
+    issues
+}
+
+fn get_value_key(key: &PropName) -> Option<String> {
+    match key {
+        PropName::Num(num) => Some(num.value.to_string()),
+        _ => get_static_prop_name(key).map(|key| key.to_string()),
+    }
+}
+
+/// Whether the expression certainly does not evaluate to a function.
+fn is_never_function(expr: &Expr) -> bool {
+    match expr {
+        Expr::Paren(paren) => is_never_function(&paren.expr),
+        Expr::Lit(..)
+        | Expr::Tpl(..)
+        | Expr::Object(..)
+        | Expr::Array(..)
+        | Expr::JSXElement(..)
+        | Expr::JSXFragment(..) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use icu_messageformat_parser::{Parser, ParserOptions};
+    use pretty_assertions::assert_eq;
+    use swc_core::{
+        common::DUMMY_SP,
+        ecma::ast::{ArrowExpr, BlockStmtOrExpr, Ident, KeyValueProp, Lit, Str},
+    };
+
+    use super::*;
+
+    fn prop(key: &str, value: Expr) -> PropOrSpread {
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+            key: PropName::Ident(Ident::new(key.into(), DUMMY_SP)),
+            value: Box::new(value),
+        })))
+    }
+
+    fn check(message: &str, props: Vec<PropOrSpread>) -> Vec<String> {
+        check_with_elements(message, props, None)
+    }
+
+    fn check_with_elements(
+        message: &str,
+        props: Vec<PropOrSpread>,
+        default_rich_text_elements: Option<&[String]>,
+    ) -> Vec<String> {
+        let options = ParserOptions::default();
+        let mut parser = Parser::new(message, &options);
+        let ast = parser.parse().unwrap();
+        let values = ObjectLit {
+            span: DUMMY_SP,
+            props,
+        };
+        check_values(&ast, &values, default_rich_text_elements)
+            .iter()
+            .map(ValuesIssue::message)
+            .collect()
+    }
+
+    #[test]
+    fn matching_values() {
+        let chunks = Expr::Arrow(ArrowExpr {
+            span: DUMMY_SP,
+            params: vec![],
+            body: BlockStmtOrExpr::Expr(Box::new(Expr::Ident(Ident::new(
+                "chunks".into(),
+                DUMMY_SP,
+            )))),
+            is_async: false,
+            is_generator: false,
+            type_params: None,
+            return_type: None,
+        });
+        let name = Expr::Ident(Ident::new("name".into(), DUMMY_SP));
+
+        assert_eq!(
+            check(
+                "Hello <b>{name}</b>, {count, plural, one {# item} other {# items}}",
+                vec![
+                    prop("b", chunks),
+                    prop("name", name.clone()),
+                    prop("count", name)
+                ]
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn mismatching_values() {
+        let str = Expr::Lit(Lit::Str(Str::from("bold")));
+
+        assert_eq!(
+            check(
+                "Hello <b>{name}</b>",
+                vec![prop("b", str.clone()), prop("nmae", str)]
+            ),
+            vec![
+                "[React Intl] Missing value of `name`, which is used by the message.",
+                "[React Intl] Value of the rich text tag `b` must be a function.",
+                "[React Intl] Value `nmae` is not used by the message.",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_tag_values() {
+        let name = Expr::Ident(Ident::new("name".into(), DUMMY_SP));
+        let message = "Hello <b>{name}</b>, see <link>the docs</link>";
+
+        assert_eq!(
+            check(message, vec![prop("name", name.clone())]),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            check_with_elements(message, vec![prop("name", name)], Some(&["b".into()])),
+            vec!["[React Intl] Missing value of `link`, which is used by the message."]
+        );
+    }
+}
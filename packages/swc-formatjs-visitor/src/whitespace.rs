@@ -0,0 +1,285 @@
+use icu_messageformat_parser::{print_ast, AstElement, Parser, ParserOptions};
+
+use crate::{EdgeWhitespace, WhitespaceOptions, WHITESPACE_REGEX};
+
+/// Whitespace normalization of babel-plugin-formatjs, collapsing whitespace across the
+/// whole message.
+fn collapse_message_whitespace(message: &str) -> String {
+    WHITESPACE_REGEX
+        .replace_all(message, " ")
+        .trim()
+        .to_string()
+}
+
+/// Normalize the whitespace of a message on its AST: runs of whitespace in literal text
+/// are collapsed into a single space, while quoted text, argument styles and skeletons
+/// are kept as is. The message is trimmed, and the edges of tags and plural / select
+/// branches are handled according to `options`.
+///
+/// The message collapsed like babel-plugin-formatjs does is returned if it's equivalent,
+/// so ids hashed from the message stay the same. Messages which cannot be parsed are
+/// collapsed like that as well.
+pub fn normalize_whitespace(
+    message: &str,
+    parser_options: &ParserOptions,
+    options: &WhitespaceOptions,
+) -> String {
+    let collapsed = collapse_message_whitespace(message);
+
+    // Locations are needed to find quoted text, while resolving date skeletons to the
+    // patterns of a locale would change them.
+    let parser_options = ParserOptions {
+        capture_location: true,
+        locale: None,
+        ..parser_options.clone()
+    };
+    let mut parser = Parser::new(message, &parser_options);
+    let mut ast = match parser.parse() {
+        Ok(ast) => ast,
+        Err(..) => return collapsed,
+    };
+
+    let source: Vec<u16> = message.encode_utf16().collect();
+    normalize_elements(&mut ast, &source, false, EdgeWhitespace::Trim, options);
+    let normalized = print_ast(&ast);
+
+    let mut parser = Parser::new(&collapsed, &parser_options);
+    match parser.parse() {
+        Ok(ast) if print_ast(&ast) == normalized => collapsed,
+        _ => normalized,
+    }
+}
+
+fn normalize_elements(
+    elements: &mut Vec<AstElement>,
+    source: &[u16],
+    in_plural: bool,
+    edges: EdgeWhitespace,
+    options: &WhitespaceOptions,
+) {
+    let len = elements.len();
+    for (idx, element) in elements.iter_mut().enumerate() {
+        match element {
+            AstElement::Literal { value, span } => {
+                let text = span
+                    .as_ref()
+                    .and_then(|span| source.get(span.start.offset..span.end.offset))
+                    .map(String::from_utf16_lossy);
+                let mut collapsed = collapse_literal(value, text.as_deref(), in_plural);
+
+                // Only unquoted whitespace is trimmed.
+                if edges == EdgeWhitespace::Trim {
+                    if idx == 0 && collapsed.first() == Some(&(' ', false)) {
+                        collapsed.remove(0);
+                    }
+                    if idx == len - 1 && collapsed.last() == Some(&(' ', false)) {
+                        collapsed.pop();
+                    }
+                }
+                *value = collapsed.into_iter().map(|(c, _)| c).collect();
+            }
+            AstElement::Select {
+                options: branches, ..
+            } => {
+                for (_, branch) in branches.0.iter_mut() {
+                    normalize_elements(
+                        &mut branch.value,
+                        source,
+                        false,
+                        options.branch_edges,
+                        options,
+                    );
+                }
+            }
+            AstElement::Plural {
+                options: branches, ..
+            } => {
+                for (_, branch) in branches.0.iter_mut() {
+                    normalize_elements(
+                        &mut branch.value,
+                        source,
+                        true,
+                        options.branch_edges,
+                        options,
+                    );
+                }
+            }
+            AstElement::Tag { children, .. } => {
+                normalize_elements(children, source, in_plural, options.tag_edges, options);
+            }
+            _ => {}
+        }
+    }
+
+    elements.retain(
+        |element| !matches!(element, AstElement::Literal { value, .. } if value.is_empty()),
+    );
+}
+
+/// Collapse runs of whitespace of a literal into a single space, keeping whitespace of
+/// quoted text. `text` is the source of the literal, which tells the quoted text apart.
+/// Returns the characters along with whether they're quoted.
+fn collapse_literal(value: &str, text: Option<&str>, in_plural: bool) -> Vec<(char, bool)> {
+    let lexed = text
+        .map(|text| lex_literal(text, in_plural))
+        .filter(|lexed| lexed.iter().map(|(c, _)| *c).eq(value.chars()))
+        // Self-closing tags are literals which do not match their source.
+        .unwrap_or_else(|| value.chars().map(|c| (c, false)).collect());
+
+    let mut collapsed = Vec::with_capacity(lexed.len());
+    let mut in_whitespace = false;
+    for (c, is_quoted) in lexed {
+        if !is_quoted && c.is_whitespace() {
+            if !in_whitespace {
+                collapsed.push((' ', false));
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push((c, is_quoted));
+            in_whitespace = false;
+        }
+    }
+    collapsed
+}
+
+/// Unescape the source of a literal like the parser does, along with whether each
+/// character is quoted.
+fn lex_literal(text: &str, in_plural: bool) -> Vec<(char, bool)> {
+    let chars: Vec<char> = text.chars().collect();
+    let starts_quote = |c: Option<&char>| match c {
+        Some('{' | '<' | '>' | '}') => true,
+        Some('#') => in_plural,
+        _ => false,
+    };
+
+    let mut lexed = vec![];
+    let mut idx = 0;
+    while idx < chars.len() {
+        if chars[idx] == '\'' && chars.get(idx + 1) == Some(&'\'') {
+            lexed.push(('\'', false));
+            idx += 2;
+        } else if chars[idx] == '\'' && starts_quote(chars.get(idx + 1)) {
+            lexed.push((chars[idx + 1], true));
+            idx += 2;
+            while idx < chars.len() {
+                if chars[idx] == '\'' {
+                    if chars.get(idx + 1) == Some(&'\'') {
+                        lexed.push(('\'', true));
+                        idx += 2;
+                        continue;
+                    }
+                    idx += 1;
+                    break;
+                }
+                lexed.push((chars[idx], true));
+                idx += 1;
+            }
+        } else {
+            lexed.push((chars[idx], false));
+            idx += 1;
+        }
+    }
+    lexed
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn normalize(message: &str, tag_edges: EdgeWhitespace, branch_edges: EdgeWhitespace) -> String {
+        normalize_whitespace(
+            message,
+            &ParserOptions::default(),
+            &WhitespaceOptions {
+                tag_edges,
+                branch_edges,
+            },
+        )
+    }
+
+    #[test]
+    fn collapsed_like_babel() {
+        assert_eq!(
+            normalize(
+                "\n  Hello   {name},\n  {count, plural,\n    one {# item}\n    other {# items}\n  }\n",
+                EdgeWhitespace::Collapse,
+                EdgeWhitespace::Collapse
+            ),
+            "Hello {name}, {count, plural, one {# item} other {# items} }"
+        );
+        assert_eq!(
+            normalize(
+                "Hello <b> {name} </b>",
+                EdgeWhitespace::Collapse,
+                EdgeWhitespace::Collapse
+            ),
+            "Hello <b> {name} </b>"
+        );
+    }
+
+    #[test]
+    fn quoted_whitespace() {
+        assert_eq!(
+            normalize(
+                "  '{  }'   and  '<b>  </b>' ",
+                EdgeWhitespace::Collapse,
+                EdgeWhitespace::Collapse
+            ),
+            "'{'  '}' and '<'b>  '<'/b>"
+        );
+        assert_eq!(
+            normalize(
+                "Don't   {  x  }",
+                EdgeWhitespace::Collapse,
+                EdgeWhitespace::Collapse
+            ),
+            "Don't { x }"
+        );
+    }
+
+    #[test]
+    fn trimmed_edges() {
+        assert_eq!(
+            normalize(
+                "Hello <b>  {name} </b>, {count, plural, one {\n  # item } other { # items  }}",
+                EdgeWhitespace::Trim,
+                EdgeWhitespace::Trim
+            ),
+            "Hello <b>{name}</b>, {count, plural, one {# item} other {# items}}"
+        );
+    }
+
+    #[test]
+    fn invalid_message() {
+        assert_eq!(
+            normalize(
+                "  Hello   {name ",
+                EdgeWhitespace::Trim,
+                EdgeWhitespace::Trim
+            ),
+            "Hello {name"
+        );
+    }
+
+    #[test]
+    fn literal_quotes() {
+        assert_eq!(
+            lex_literal("It''s '{  }' '#", true),
+            vec![
+                ('I', false),
+                ('t', false),
+                ('\'', false),
+                ('s', false),
+                (' ', false),
+                ('{', true),
+                (' ', true),
+                (' ', true),
+                ('}', true),
+                (' ', false),
+                ('#', true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn extracted_ids(src: &str) -> Vec<String> {
+    transform(src, json!({}))
+        .messages()
+        .iter()
+        .map(|message| message["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn destructured_aliases() {
+    let src = r#"
+import {useIntl} from 'react-intl'
+
+export function Greeting() {
+  const {formatMessage: t} = useIntl()
+  const {intl: {formatMessage}} = useContext()
+  return [
+    t({id: 'renamed', defaultMessage: 'Renamed'}),
+    formatMessage({id: 'nested', defaultMessage: 'Nested'}),
+  ]
+}
+"#;
+
+    assert_eq!(extracted_ids(src), vec!["renamed", "nested"]);
+}
+
+#[test]
+fn bound_aliases() {
+    let src = r#"
+export function Greeting({intl}) {
+  const t = intl.formatMessage.bind(intl)
+  const u = t
+  return [
+    t({id: 'bound', defaultMessage: 'Bound'}),
+    u({id: 'reassigned', defaultMessage: 'Reassigned'}),
+    intl.formatMessage.bind(intl)({id: 'called', defaultMessage: 'Called'}),
+  ]
+}
+"#;
+
+    assert_eq!(extracted_ids(src), vec!["bound", "reassigned", "called"]);
+}
+
+#[test]
+fn optional_and_computed_callees() {
+    let src = r#"
+export function Greeting({intl}) {
+  return [
+    intl?.formatMessage({id: 'optional-member', defaultMessage: 'Optional member'}),
+    intl.formatMessage?.({id: 'optional-call', defaultMessage: 'Optional call'}),
+    intl['formatMessage']({id: 'computed', defaultMessage: 'Computed'}),
+    intl[formatMessage]({id: 'dynamic', defaultMessage: 'Dynamic'}),
+  ]
+}
+"#;
+
+    assert_eq!(
+        extracted_ids(src),
+        vec!["optional-member", "optional-call", "computed"]
+    );
+}
+
+#[test]
+fn hoisted_declarations() {
+    let src = r#"
+export function Greeting({intl}) {
+  const t = intl.formatMessage
+
+  function render() {
+    return t({id: 'hoisted-function', defaultMessage: 'Shadowed by a function'})
+    function t(descriptor) {
+      return descriptor.id
+    }
+  }
+
+  function later() {
+    return u({id: 'hoisted-const', defaultMessage: 'Aliased by a later const'})
+  }
+  const u = intl.formatMessage
+
+  return [render(), later(), t({id: 'outer', defaultMessage: 'Outer'})]
+}
+"#;
+
+    assert_eq!(extracted_ids(src), vec!["hoisted-const", "outer"]);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn options() -> serde_json::Value {
+    json!({ "childrenMessageComponents": ["T"] })
+}
+
+#[test]
+fn children_message() {
+    let src = r#"
+'use client'
+import {Link} from './link'
+
+export const Cart = ({name, count}) => (
+  <T description="Greeting">
+    Hello <b>{name}</b>,
+    you have {count} items. <Link to="/cart">Checkout</Link><br />
+  </T>
+)
+"#;
+    let output = transform(src, options());
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(
+        output.code,
+        r#"'use client';
+import { FormattedMessage } from "react-intl";
+import { Link } from './link';
+export const Cart = ({ name , count  })=>(<FormattedMessage id="xquNpQ" defaultMessage="Hello <b>{name}</b>, you have {count} items. <Link>Checkout</Link>{br}" values={{
+        b: (chunks)=><b >{chunks}</b>,
+        name,
+        count,
+        Link: (chunks)=><Link to="/cart">{chunks}</Link>,
+        br: <br />
+    }}/>);"#
+    );
+    assert_eq!(
+        output.messages(),
+        &vec![json!({
+            "id": "xquNpQ",
+            "description": "Greeting",
+            "defaultMessage": "Hello <b>{name}</b>, you have {count} items. <Link>Checkout</Link>{br}"
+        })]
+    );
+}
+
+#[test]
+fn conflicting_module_bindings() {
+    let src = r#"
+import {FormattedMessage} from './formatted-message'
+
+function _FormattedMessage() {}
+class _FormattedMessage2 {}
+
+export const Greeting = () => <T>Hello</T>
+"#;
+    let output = transform(src, options());
+
+    assert_eq!(
+        output.code,
+        r#"import { FormattedMessage as _FormattedMessage3 } from "react-intl";
+import { FormattedMessage } from './formatted-message';
+function _FormattedMessage() {}
+class _FormattedMessage2 {
+}
+export const Greeting = ()=><_FormattedMessage3 id="NhX4DJ" defaultMessage="Hello"/>;"#
+    );
+}
@@ -0,0 +1,162 @@
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+
+use swc_core::{
+    common::{
+        comments::SingleThreadedComments,
+        errors::{DiagnosticBuilder, Emitter, Handler, Level, HANDLER},
+        sync::Lrc,
+        FileName, SourceMap, SourceMapper, GLOBALS,
+    },
+    ecma::{
+        codegen::{text_writer::JsWriter, Emitter as CodeEmitter},
+        parser::{parse_file_as_module, EsConfig, Syntax},
+        visit::VisitMutWith,
+    },
+};
+use swc_formatjs_visitor::{create_formatjs_visitor, FormatJSPluginOptions, HardcodedString};
+
+static EXTRACTED_MESSAGES_MARKER: &str = "__formatjs__messages_extracted__::";
+
+/// A diagnostic reported by the transform.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    /// The source code at the primary span of the diagnostic.
+    pub snippet: Option<String>,
+}
+
+pub struct Output {
+    pub code: String,
+    /// The extracted `messages` of the file, along with `meta` and `hardcodedStrings`.
+    pub extracted: serde_json::Value,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The hardcoded strings reported by the visitor.
+    pub hardcoded_strings: Vec<HardcodedString>,
+}
+
+impl Output {
+    pub fn messages(&self) -> &Vec<serde_json::Value> {
+        self.extracted["messages"]
+            .as_array()
+            .expect("Should have messages")
+    }
+
+    pub fn errors(&self) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == Level::Error)
+            .collect()
+    }
+
+    pub fn warnings(&self) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.level == Level::Warning)
+            .collect()
+    }
+}
+
+struct CollectingEmitter {
+    source_map: Arc<SourceMap>,
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Emitter for CollectingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let snippet = db
+            .span
+            .primary_span()
+            .and_then(|span| self.source_map.span_to_snippet(span).ok());
+        self.diagnostics.lock().unwrap().push(Diagnostic {
+            level: db.level,
+            message: db.message(),
+            snippet,
+        });
+    }
+}
+
+/// Transform the JSX module `src` with the plugin options, as `file.js`.
+pub fn transform(src: &str, options: serde_json::Value) -> Output {
+    transform_file("file.js", src, options)
+}
+
+pub fn transform_file(filename: &str, src: &str, options: serde_json::Value) -> Output {
+    let source_map: Arc<SourceMap> = Default::default();
+    let diagnostics = Arc::new(Mutex::new(vec![]));
+    let handler = Handler::with_emitter(
+        true,
+        false,
+        Box::new(CollectingEmitter {
+            source_map: source_map.clone(),
+            diagnostics: diagnostics.clone(),
+        }),
+    );
+
+    let mut options: FormatJSPluginOptions =
+        serde_json::from_value(options).expect("Should be valid options");
+    options.__debug_extracted_messages_comment = true;
+
+    let file = source_map.new_source_file(FileName::Custom(filename.into()), src.into());
+    let comments = SingleThreadedComments::default();
+
+    let mut hardcoded_strings = vec![];
+    let code = GLOBALS.set(&Default::default(), || {
+        HANDLER.set(&handler, || {
+            let mut module = parse_file_as_module(
+                &file,
+                Syntax::Es(EsConfig {
+                    jsx: true,
+                    ..Default::default()
+                }),
+                Default::default(),
+                Some(&comments),
+                &mut vec![],
+            )
+            .expect("Should parse");
+
+            let mut visitor =
+                create_formatjs_visitor(source_map.clone(), &comments, options, filename);
+            module.visit_mut_with(&mut visitor);
+            hardcoded_strings = visitor.hardcoded_strings().to_vec();
+
+            // The visitor takes an `Arc` of the source map while the code generator takes an
+            // `Lrc`, which is not the same without the `concurrent` feature. Load the file
+            // into a map of its own, at the same positions.
+            let codegen_source_map: Lrc<SourceMap> = Default::default();
+            codegen_source_map.new_source_file(FileName::Custom(filename.into()), src.into());
+
+            let mut buf = vec![];
+            let mut emitter = CodeEmitter {
+                cfg: Default::default(),
+                cm: codegen_source_map.clone(),
+                comments: Some(&comments),
+                wr: JsWriter::new(codegen_source_map, "\n", &mut buf, None),
+            };
+            emitter.emit_module(&module).expect("Should emit");
+            String::from_utf8(buf).expect("Should be utf8")
+        })
+    });
+
+    let (_, trailing) = comments.borrow_all();
+    let extracted = trailing
+        .values()
+        .flatten()
+        .find_map(|comment| comment.text.strip_prefix(EXTRACTED_MESSAGES_MARKER))
+        .expect("Should have extracted messages")
+        .to_string();
+    let code = match code.find(&format!("/*{}", EXTRACTED_MESSAGES_MARKER)) {
+        Some(index) => &code[..index],
+        None => &code,
+    };
+
+    let diagnostics = diagnostics.lock().unwrap().clone();
+    Output {
+        code: code.trim().to_string(),
+        extracted: serde_json::from_str(&extracted).expect("Should be JSON"),
+        diagnostics,
+        hardcoded_strings,
+    }
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn descriptions(src: &str) -> Vec<serde_json::Value> {
+    transform(
+        src,
+        json!({ "descriptionCommentMarkers": ["i18n:", "@description"] }),
+    )
+    .messages()
+    .iter()
+    .map(|message| message["description"].clone())
+    .collect()
+}
+
+#[test]
+fn leading_comments_of_descriptors() {
+    let src = r#"
+import {defineMessage, defineMessages, FormattedMessage} from 'react-intl'
+
+// i18n: Shown on the checkout button
+defineMessage({id: 'checkout', defaultMessage: 'Checkout'})
+
+defineMessages({
+    /** @description Shown on top */
+    title: {id: 'title', defaultMessage: 'Welcome'},
+    heading: {id: 'heading', defaultMessage: 'Hello'}, // i18n: Not leading heading
+})
+
+function App() {
+    return (
+        // i18n: Greets the user
+        <FormattedMessage id="greeting" defaultMessage="Hello" />
+    )
+}
+"#;
+
+    assert_eq!(
+        descriptions(src),
+        [
+            json!("Shown on the checkout button"),
+            json!("Shown on top"),
+            json!(null),
+            json!("Greets the user"),
+        ]
+    );
+}
+
+#[test]
+fn comments_of_other_nodes_are_not_descriptions() {
+    let src = r#"
+import {defineMessage} from 'react-intl'
+
+const label = 'Checkout' // i18n: Not a description
+defineMessage({id: 'checkout', defaultMessage: 'Checkout'})
+"#;
+
+    assert_eq!(descriptions(src), [json!(null)]);
+}
+
+#[test]
+fn merged_with_descriptions() {
+    let src = r#"
+import {defineMessages} from 'react-intl'
+
+defineMessages({
+    // i18n: Shown on the checkout button
+    checkout: {id: 'checkout', defaultMessage: 'Checkout', description: 'Button'},
+    // i18n: Shown on top
+    title: {id: 'title', defaultMessage: 'Welcome', description: {context: 'header'}},
+    // i18n: Ignored
+    heading: {id: 'heading', defaultMessage: 'Hello', description: {comment: 'Heading'}},
+})
+"#;
+
+    assert_eq!(
+        descriptions(src),
+        [
+            json!("Button\nShown on the checkout button"),
+            json!({"context": "header", "comment": "Shown on top"}),
+            json!({"comment": "Heading"}),
+        ]
+    );
+}
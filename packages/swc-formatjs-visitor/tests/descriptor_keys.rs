@@ -0,0 +1,98 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use swc_formatjs_visitor::FormatJSPluginOptions;
+
+static WRAPPERS: &str = r#"
+import {defineMessage} from 'react-intl'
+
+const MAX_LENGTH = 40
+
+defineMessage({key: 'call', message: 'Call', meaning: 'verb', maxLength: MAX_LENGTH})
+export const Element = () => <T key="element" message="Element" meaning="noun" plural />
+"#;
+
+fn options(extra: serde_json::Value) -> serde_json::Value {
+    let mut options = json!({
+        "additionalComponentNames": ["T"],
+        "descriptorKeys": {
+            "aliases": { "key": "id", "message": "defaultMessage" },
+            "extraFields": ["meaning", "maxLength", "plural"]
+        }
+    });
+    for (key, value) in extra.as_object().unwrap() {
+        options["descriptorKeys"][key] = value.clone();
+    }
+    options
+}
+
+#[test]
+fn aliases_and_extra_fields() {
+    let output = transform(WRAPPERS, options(json!({})));
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(
+        output.messages(),
+        &vec![
+            json!({
+                "id": "call",
+                "defaultMessage": "Call",
+                "meaning": "verb",
+                "maxLength": 40
+            }),
+            json!({
+                "id": "element",
+                "defaultMessage": "Element",
+                "meaning": "noun",
+                "plural": true
+            }),
+        ]
+    );
+    assert!(output
+        .code
+        .contains(r#"<T key="element" message="Element" meaning="noun" plural/>"#));
+}
+
+#[test]
+fn remove_extra_fields() {
+    let output = transform(WRAPPERS, options(json!({ "removeExtraFields": true })));
+
+    assert_eq!(output.messages()[0]["maxLength"], 40);
+    assert_eq!(
+        output.code,
+        r#"import { defineMessage } from 'react-intl';
+const MAX_LENGTH = 40;
+defineMessage({
+    key: 'call',
+    message: "Call"
+});
+export const Element = ()=><T key="element" message="Element"/>;"#
+    );
+}
+
+#[test]
+fn reserved_extra_fields() {
+    for field in [
+        "id",
+        "defaultMessage",
+        "description",
+        "loc",
+        "shortId",
+        "meta",
+    ] {
+        let error = serde_json::from_value::<FormatJSPluginOptions>(json!({
+            "descriptorKeys": { "extraFields": ["meaning", field] }
+        }))
+        .unwrap_err();
+        assert!(
+            error.to_string().contains(&format!(
+                "`{}` is a field of extracted messages and cannot be an extra field",
+                field
+            )),
+            "{}",
+            error
+        );
+    }
+}
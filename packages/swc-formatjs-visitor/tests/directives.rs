@@ -0,0 +1,104 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn ids(output: &common::Output) -> Vec<&str> {
+    output
+        .messages()
+        .iter()
+        .map(|message| message["id"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn ignore_next_line() {
+    let output = transform(
+        r#"
+import {defineMessage, FormattedMessage} from 'react-intl'
+
+// formatjs-ignore-next-line
+defineMessage({id: 'ignored', defaultMessage: 'Ignored', description: 'Kept'})
+defineMessage({id: 'extracted', defaultMessage: 'Extracted'})
+
+const element = (
+    <div>
+        {/* formatjs-ignore-next-line */}
+        <FormattedMessage id="ignoredElement" defaultMessage="Ignored" />
+    </div>
+)
+"#,
+        json!({}),
+    );
+
+    assert_eq!(ids(&output), ["extracted"]);
+    // Skipped descriptors are not transformed either.
+    assert!(output.code.contains("description: 'Kept'"));
+}
+
+#[test]
+fn disabled_regions() {
+    let output = transform(
+        r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({id: 'first', defaultMessage: 'First'})
+/* formatjs-disable */
+defineMessage({id: 'disabled', defaultMessage: 'Disabled'})
+/* formatjs-enable */
+defineMessage({id: 'second', defaultMessage: 'Second'})
+/* formatjs-disable */
+defineMessage({id: 'disabledToTheEnd', defaultMessage: 'Disabled'})
+"#,
+        json!({}),
+    );
+
+    assert_eq!(ids(&output), ["first", "second"]);
+}
+
+#[test]
+fn option_overrides() {
+    let src = r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({defaultMessage: 'Hello'})
+"#;
+    let output = transform(src, json!({}));
+    let overridden = transform(
+        &format!(
+            "// @formatjs removeDefaultMessage idInterpolationPattern=[sha512:contenthash:hex:8]\n{}",
+            src
+        ),
+        json!({}),
+    );
+
+    assert!(overridden.diagnostics.is_empty());
+    assert_eq!(output.messages()[0]["id"].as_str().unwrap().len(), 6);
+    assert_eq!(overridden.messages()[0]["id"].as_str().unwrap().len(), 8);
+    assert!(output.code.contains("defaultMessage"));
+    assert!(!overridden.code.contains("defaultMessage"));
+}
+
+#[test]
+fn invalid_option_overrides() {
+    let output = transform(
+        r#"
+// @formatjs pragma=intl ast=yes
+import {defineMessage} from 'react-intl'
+
+defineMessage({id: 'greeting', defaultMessage: 'Hello'})
+"#,
+        json!({}),
+    );
+
+    let errors = output.errors();
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0]
+        .message
+        .starts_with("[React Intl] Invalid `@formatjs` option `pragma`: expected one of"));
+    assert!(errors[1]
+        .message
+        .starts_with("[React Intl] Invalid `@formatjs` option `ast`: invalid type"));
+    assert_eq!(output.messages()[0]["defaultMessage"], "Hello");
+}
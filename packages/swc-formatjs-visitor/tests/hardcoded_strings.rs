@@ -0,0 +1,124 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn texts(output: &common::Output) -> Vec<&str> {
+    output
+        .hardcoded_strings
+        .iter()
+        .map(|string| string.text.as_str())
+        .collect()
+}
+
+#[test]
+fn reports_untranslated_text() {
+    let output = transform(
+        r#"
+import {FormattedMessage} from 'react-intl'
+
+const element = (
+    <div title="Greeting">
+        Hello {'world'} {name}
+        <img alt="" />
+        <p>
+            <FormattedMessage id="greeting" defaultMessage="Hello" />
+            —
+        </p>
+    </div>
+)
+"#,
+        json!({ "hardcodedStrings": {} }),
+    );
+
+    assert_eq!(texts(&output), ["Greeting", "Hello", "world"]);
+    assert_eq!(
+        output.hardcoded_strings[0].attribute.as_deref(),
+        Some("title")
+    );
+    assert_eq!(output.hardcoded_strings[0].loc.start.line, 5);
+
+    assert_eq!(output.warnings().len(), 3);
+    assert_eq!(
+        output.warnings()[0].message,
+        "[React Intl] Hardcoded string \"Greeting\" of `title` is not translated."
+    );
+    assert_eq!(
+        output.warnings()[1].message,
+        "[React Intl] Hardcoded string \"Hello\" is not translated."
+    );
+
+    let extracted = output.extracted["hardcodedStrings"].as_array().unwrap();
+    assert_eq!(extracted.len(), 3);
+    assert_eq!(extracted[0]["text"], "Greeting");
+    assert_eq!(extracted[0]["attribute"], "title");
+}
+
+#[test]
+fn not_reported_without_the_option() {
+    let output = transform(
+        r#"const element = <div title="Greeting">Hello</div>"#,
+        json!({}),
+    );
+
+    assert!(output.hardcoded_strings.is_empty());
+    assert!(output.diagnostics.is_empty());
+    assert!(output.extracted.get("hardcodedStrings").is_none());
+}
+
+#[test]
+fn ignore_lists() {
+    let output = transform(
+        r#"
+const element = (
+    <div data-tooltip="Tooltip" title="Title" placeholder="Placeholder">
+        <code>npm install</code>
+        <Icon label="Icon">Icon</Icon>
+        <span>TODO_KEY</span>
+        Text
+    </div>
+)
+"#,
+        json!({
+            "hardcodedStrings": {
+                "attributes": ["data-tooltip"],
+                "ignoreComponents": ["code", "Icon"],
+                "ignoreAttributes": ["title"],
+                "ignorePatterns": ["^[A-Z_]+$"],
+                "severity": "error"
+            }
+        }),
+    );
+
+    assert_eq!(texts(&output), ["Tooltip", "Placeholder", "Text"]);
+    assert_eq!(output.errors().len(), 3);
+}
+
+#[test]
+fn text_of_nested_ignored_elements() {
+    let output = transform(
+        r#"
+import {FormattedMessage} from 'react-intl'
+
+const element = (
+    <div>
+        <code>
+            <span>Nested <b>deeper</b></span>
+            <code>Ignored twice</code>
+            After
+        </code>
+        <FormattedMessage
+            id="greeting"
+            defaultMessage="Hello <b>world</b>"
+            values={{ b: (chunks) => <b>{chunks} and more</b> }}
+        />
+        Reported
+    </div>
+)
+"#,
+        json!({ "hardcodedStrings": { "ignoreComponents": ["code"] } }),
+    );
+
+    assert_eq!(texts(&output), ["Reported"]);
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn only_unreplaced_calls_are_marked_pure() {
+    let output = transform(
+        r#"
+import {defineMessages} from 'react-intl'
+
+export const messages = defineMessages({
+    greeting: {id: 'greeting', defaultMessage: 'Hello'},
+})
+export const spread = defineMessages(...others)
+"#,
+        json!({ "idOnly": { "bareId": true } }),
+    );
+
+    assert_eq!(
+        output.code,
+        r#"import { defineMessages } from 'react-intl';
+export const messages = {
+    greeting: "greeting"
+};
+export const spread = /*#__PURE__*/ defineMessages(...others);"#
+    );
+}
+
+#[test]
+fn descriptors_are_reduced_to_ids() {
+    let output = transform(
+        r#"
+import {defineMessage} from 'react-intl'
+
+export const greeting = defineMessage({id: 'greeting', defaultMessage: 'Hello'})
+"#,
+        json!({ "idOnly": { "bareId": false } }),
+    );
+
+    assert_eq!(
+        output.code,
+        r#"import { defineMessage } from 'react-intl';
+export const greeting = {
+    id: 'greeting'
+};"#
+    );
+    assert_eq!(output.messages()[0]["defaultMessage"], "Hello");
+}
+
+#[test]
+fn short_id_collisions_within_a_file() {
+    // With 62 possible short ids of one character, some of 63 ids must collide.
+    let mut src = "import {defineMessage} from 'react-intl'\n".to_string();
+    for index in 0..63 {
+        src += &format!("defineMessage({{id: 'message{index}', defaultMessage: 'Message'}})\n");
+    }
+    let output = transform(&src, json!({ "idOnly": { "shortIdLength": 1 } }));
+
+    assert_eq!(output.messages().len(), 63);
+    assert!(!output.errors().is_empty());
+    assert!(output
+        .errors()
+        .iter()
+        .all(|error| error.message.contains("use a longer `shortIdLength`")));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::{transform, transform_file};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+static MESSAGES: &str = r#"
+import {defineMessages} from 'react-intl'
+
+defineMessages({
+    title: {defaultMessage: 'Hello'},
+    heading: {defaultMessage: 'Hello'},
+})
+"#;
+
+fn ids(output: &common::Output) -> Vec<String> {
+    output
+        .messages()
+        .iter()
+        .map(|message| message["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn key_is_hashed_with_the_message() {
+    let output = transform(
+        MESSAGES,
+        json!({ "idStrategy": { "template": "[key].[sha512:contenthash:hex:6]" } }),
+    );
+    let ids = ids(&output);
+
+    assert!(output.diagnostics.is_empty());
+    assert!(ids[0].starts_with("title."));
+    assert!(ids[1].starts_with("heading."));
+    assert_ne!(ids[0]["title.".len()..], ids[1]["heading.".len()..]);
+
+    let output = transform(
+        MESSAGES,
+        json!({ "idStrategy": { "template": "[sha512:contenthash:hex:6]" } }),
+    );
+    assert_eq!(output.messages().len(), 1);
+}
+
+#[test]
+fn source_ids_are_overridden() {
+    let output = transform(
+        r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({id: 'greeting', defaultMessage: 'Hello'})
+"#,
+        json!({
+            "idStrategy": { "template": "app.[id]", "overrideExisting": true, "validate": "^[a-z]+$" }
+        }),
+    );
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(ids(&output), ["app.greeting"]);
+}
+
+#[test]
+fn description_is_not_interpolated() {
+    let output = transform(
+        r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({defaultMessage: 'Hello', description: 'Shown on [name] page, v1.2'})
+"#,
+        json!({ "idStrategy": { "template": "[name].[description]" } }),
+    );
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(ids(&output), ["file.Shown_on_name_page_v1_2"]);
+}
+
+#[test]
+fn prefix_by_directory() {
+    let options = json!({
+        "idStrategy": { "template": "[key]", "prefixByDirectory": true }
+    });
+
+    let output = transform_file("src/components/Greeting.js", MESSAGES, options.clone());
+    assert_eq!(
+        ids(&output),
+        ["src.components.title", "src.components.heading"]
+    );
+
+    let mut rooted = options.clone();
+    rooted["idInterpolateRoot"] = json!("/project/src");
+    let output = transform_file("/project/src/components/Greeting.js", MESSAGES, rooted);
+    assert_eq!(ids(&output), ["components.title", "components.heading"]);
+
+    let output = transform_file(
+        "/project/src/components/Greeting.js",
+        MESSAGES,
+        options.clone(),
+    );
+    assert_eq!(ids(&output), ["title", "heading"]);
+    assert_eq!(output.errors().len(), 2);
+    assert!(output.errors()[0]
+        .message
+        .contains("`idStrategy.prefixByDirectory` requires `idInterpolateRoot`"));
+
+    // Ids written in the source are kept without `overrideExisting`.
+    let output = transform_file(
+        "src/components/Greeting.js",
+        r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({id: 'greeting', defaultMessage: 'Hello'})
+"#,
+        options,
+    );
+    assert_eq!(ids(&output), ["greeting"]);
+    assert!(output.code.contains("id: 'greeting'"));
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn extracted_ids(src: &str, options: serde_json::Value) -> Vec<String> {
+    transform(src, options)
+        .messages()
+        .iter()
+        .map(|message| message["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn aliased_imports() {
+    let src = r#"
+import {FormattedMessage as Message, defineMessage as message} from 'react-intl'
+
+message({id: 'call', defaultMessage: 'Call'})
+export const Element = () => <Message id="element" defaultMessage="Element" />
+"#;
+
+    assert_eq!(extracted_ids(src, json!({})), vec!["call", "element"]);
+}
+
+#[test]
+fn namespace_imports() {
+    let src = r#"
+import * as ReactIntl from 'react-intl'
+import Intl from 'react-intl'
+
+ReactIntl.defineMessage({id: 'call', defaultMessage: 'Call'})
+Intl.defineMessage({id: 'default', defaultMessage: 'Default'})
+export const Element = () => <ReactIntl.FormattedMessage id="element" defaultMessage="Element" />
+"#;
+
+    // Default imports are not namespaces.
+    assert_eq!(extracted_ids(src, json!({})), vec!["call", "element"]);
+}
+
+#[test]
+fn additional_module_source_names() {
+    let src = r#"
+import {defineMessage} from '@acme/i18n'
+
+defineMessage({id: 'acme', defaultMessage: 'Acme'})
+"#;
+
+    assert_eq!(extracted_ids(src, json!({})), Vec::<String>::new());
+    assert_eq!(
+        extracted_ids(
+            src,
+            json!({ "additionalModuleSourceNames": ["@acme/i18n"] })
+        ),
+        vec!["acme"]
+    );
+}
+
+#[test]
+fn unimported_names() {
+    let src = r#"
+import {FormattedMessage} from './components'
+
+defineMessage({id: 'global', defaultMessage: 'Global'})
+export const Element = () => <FormattedMessage id="element" defaultMessage="Element" />
+"#;
+
+    assert_eq!(extracted_ids(src, json!({})), Vec::<String>::new());
+    // Names imported from other modules never match.
+    assert_eq!(
+        extracted_ids(src, json!({ "matchUnimportedNames": true })),
+        vec!["global"]
+    );
+}
+
+#[test]
+fn local_shadowing() {
+    let src = r#"
+import {FormattedMessage, defineMessage, useIntl} from 'react-intl'
+
+export function Greeting() {
+  const intl = useIntl()
+  const formatMessage = (descriptor) => descriptor.id
+  const defineMessage = (descriptor) => descriptor
+  const FormattedMessage = () => null
+  return [
+    formatMessage({id: 'shadowed-call', defaultMessage: 'Shadowed'}),
+    defineMessage({id: 'shadowed-define', defaultMessage: 'Shadowed'}),
+    <FormattedMessage id="shadowed-element" defaultMessage="Shadowed" />,
+    intl.formatMessage({id: 'member', defaultMessage: 'Member'}),
+  ]
+}
+
+defineMessage({id: 'imported', defaultMessage: 'Imported'})
+"#;
+
+    assert_eq!(extracted_ids(src, json!({})), vec!["member", "imported"]);
+}
+
+#[test]
+fn bindings_named_like_functions() {
+    let src = r#"
+export function Greeting(formatMessage) {
+  return formatMessage({id: 'param', defaultMessage: 'Param'})
+}
+"#;
+
+    assert_eq!(extracted_ids(src, json!({})), Vec::<String>::new());
+    assert_eq!(
+        extracted_ids(src, json!({ "matchUnimportedNames": true })),
+        vec!["param"]
+    );
+}
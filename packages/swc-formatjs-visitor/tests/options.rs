@@ -0,0 +1,118 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use swc_formatjs_visitor::FormatJSPluginOptions;
+
+static UNEVALUATED_MESSAGE: &str = r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({id: 'greeting', defaultMessage: getMessage()})
+"#;
+
+#[test]
+fn unknown_options_are_rejected() {
+    let error = serde_json::from_value::<FormatJSPluginOptions>(json!({
+        "idInterpolationPatern": "[sha512:contenthash:base64:6]"
+    }))
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown field `idInterpolationPatern`"));
+
+    let error = serde_json::from_value::<FormatJSPluginOptions>(json!({
+        "onError": { "parse": "warn", "evaluate": "warn" }
+    }))
+    .unwrap_err();
+    assert!(error.to_string().contains("unknown field `evaluate`"));
+}
+
+#[test]
+fn id_interpolation_pattern_alias() {
+    let src = r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({defaultMessage: 'Hello'})
+"#;
+    let output = transform(
+        src,
+        json!({ "idInterpolationPattern": "[sha512:contenthash:hex:8]" }),
+    );
+    let aliased = transform(
+        src,
+        json!({ "idInterpolatePattern": "[sha512:contenthash:hex:8]" }),
+    );
+
+    assert_eq!(output.messages()[0]["id"], aliased.messages()[0]["id"]);
+    assert_eq!(output.messages()[0]["id"].as_str().unwrap().len(), 8);
+}
+
+#[test]
+fn severity_per_category() {
+    let output = transform(UNEVALUATED_MESSAGE, json!({}));
+    assert_eq!(output.errors().len(), 1);
+    assert_eq!(
+        output.errors()[0].message,
+        "[React Intl] Messages must be statically evaluate-able for extraction."
+    );
+    assert_eq!(output.errors()[0].snippet.as_deref(), Some("getMessage()"));
+
+    let output = transform(
+        UNEVALUATED_MESSAGE,
+        json!({ "onError": { "evaluation": "warn" } }),
+    );
+    assert!(output.errors().is_empty());
+    assert_eq!(output.warnings().len(), 1);
+
+    let output = transform(
+        UNEVALUATED_MESSAGE,
+        json!({ "onError": { "evaluation": "ignore", "parse": "warn" } }),
+    );
+    assert!(output.diagnostics.is_empty());
+}
+
+#[test]
+fn ast_without_locations() {
+    let src = r#"
+import {defineMessage} from 'react-intl'
+
+defineMessage({id: 'greeting', defaultMessage: 'Hello {name}'})
+"#;
+    let output = transform(src, json!({ "ast": true }));
+    let captured = transform(
+        src,
+        json!({ "ast": true, "parserOptions": { "captureLocation": true } }),
+    );
+
+    assert!(output.code.contains("defaultMessage: ["));
+    assert!(!captured.code.contains("location"));
+    assert_eq!(output.code, captured.code);
+}
+
+#[test]
+fn invalid_config() {
+    let options = json!({
+        "idStrategy": { "validate": "[a-z" },
+        "hardcodedStrings": { "ignorePatterns": ["^[A-Z_]+$", "(unclosed"] },
+    });
+    let output = transform(UNEVALUATED_MESSAGE, options.clone());
+    let errors: Vec<_> = output
+        .errors()
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect();
+    assert_eq!(errors.len(), 3);
+    assert!(errors[0].starts_with("[React Intl] Invalid `idStrategy.validate`"));
+    assert!(errors[1].starts_with("[React Intl] Invalid `hardcodedStrings.ignorePatterns`"));
+    assert_eq!(
+        errors[2],
+        "[React Intl] Messages must be statically evaluate-able for extraction."
+    );
+
+    let mut options = options;
+    options["onError"] = json!({ "config": "warn" });
+    let output = transform(UNEVALUATED_MESSAGE, options);
+    assert_eq!(output.errors().len(), 1);
+    assert_eq!(output.warnings().len(), 2);
+}
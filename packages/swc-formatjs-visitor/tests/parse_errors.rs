@@ -0,0 +1,38 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn jsx_errors_at_the_syntax_error() {
+    let output = transform(
+        r#"
+import {FormattedMessage} from 'react-intl'
+
+const a = <FormattedMessage id="a" defaultMessage="Hello {name, plural, one {# item} other}" />
+const b = <FormattedMessage id="b" defaultMessage="Hello \{name" />
+const c = <FormattedMessage id="c" defaultMessage="Hello \\{name" />
+"#,
+        json!({}),
+    );
+
+    let errors = output.errors();
+    assert_eq!(errors.len(), 3);
+    assert_eq!(
+        errors[0].message,
+        "[React Intl] Message failed to parse: SyntaxError: \
+         EXPECT_PLURAL_ARGUMENT_SELECTOR_FRAGMENT"
+    );
+    assert_eq!(errors[0].snippet.as_deref(), Some("}"));
+    // A single backslash is not an escape attempt, the error is mapped into the attribute.
+    assert_eq!(
+        errors[1].message,
+        "[React Intl] Message failed to parse: SyntaxError: EXPECT_ARGUMENT_CLOSING_BRACE"
+    );
+    assert_eq!(errors[1].snippet.as_deref(), Some("{name"));
+    assert!(errors[2]
+        .message
+        .contains("It looks like `\\`s were used for escaping"));
+    assert_eq!(errors[2].snippet.as_deref(), Some(r#""Hello \\{name""#));
+}
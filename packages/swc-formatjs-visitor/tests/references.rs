@@ -0,0 +1,108 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+#[test]
+fn spread_define_messages_descriptors() {
+    let output = transform(
+        r#"
+import {defineMessages, FormattedMessage} from 'react-intl'
+
+const msgs = defineMessages({
+    header: {id: 'header', defaultMessage: 'Hello {name}'},
+})
+
+const valid = <FormattedMessage {...msgs.header} values={{ name: 'World' }} />
+const invalid = <FormattedMessage {...msgs.header} values={{ nam: 'World' }} />
+"#,
+        json!({ "onError": { "values": "error" }, "removeDefaultMessage": true }),
+    );
+
+    // Extracted and transformed where it's declared, and left as spread.
+    assert_eq!(output.messages().len(), 1);
+    assert_eq!(output.messages()[0]["defaultMessage"], "Hello {name}");
+    assert!(!output.code.contains("defaultMessage"));
+    assert!(output
+        .code
+        .contains("<FormattedMessage {...msgs.header} values={{"));
+
+    let errors: Vec<_> = output
+        .errors()
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            "[React Intl] Missing value of `name`, which is used by the message.",
+            "[React Intl] Value `nam` is not used by the message.",
+        ]
+    );
+}
+
+#[test]
+fn inline_const_descriptors() {
+    let output = transform(
+        r#"
+import {useIntl} from 'react-intl'
+
+const greeting = {id: 'greeting', defaultMessage: 'Hello {name}', description: 'Greeting'}
+
+function App() {
+    const intl = useIntl()
+    return intl.formatMessage(greeting, { name: 'World' })
+}
+"#,
+        json!({ "removeDefaultMessage": true }),
+    );
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(
+        output.messages(),
+        &vec![json!({
+            "id": "greeting",
+            "description": "Greeting",
+            "defaultMessage": "Hello {name}"
+        })]
+    );
+    assert!(output.code.contains(
+        r#"return intl.formatMessage({
+        id: 'greeting'
+    }, {"#
+    ));
+}
+
+#[test]
+fn flatten_object_spreads() {
+    let output = transform(
+        r#"
+import {defineMessage, FormattedMessage} from 'react-intl'
+
+const base = {id: 'base', description: 'Shared'}
+const title = {...base, id: 'title', defaultMessage: 'Title'}
+
+defineMessage({...base, defaultMessage: 'Base'})
+const element = <FormattedMessage {...title} />
+"#,
+        json!({ "removeDefaultMessage": true }),
+    );
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(
+        output.messages(),
+        &vec![
+            json!({ "id": "base", "description": "Shared", "defaultMessage": "Base" }),
+            json!({ "id": "title", "description": "Shared", "defaultMessage": "Title" }),
+        ]
+    );
+    assert!(output.code.contains(
+        r#"defineMessage({
+    id: 'base'
+});"#
+    ));
+    assert!(output
+        .code
+        .contains("const element = <FormattedMessage id={'title'}/>;"));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn options() -> serde_json::Value {
+    json!({ "taggedTemplates": { "tags": ["t"] } })
+}
+
+#[test]
+fn rewrite_to_format_message() {
+    let src = r#"
+import {t} from './i18n'
+
+export const cart = (count, user) => t`You have ${count} items, ${user.name}`
+"#;
+    let output = transform(src, options());
+
+    assert!(output.diagnostics.is_empty());
+    assert_eq!(
+        output.code,
+        r#"import { t } from './i18n';
+export const cart = (count, user)=>intl.formatMessage({
+        id: "WHt2US",
+        defaultMessage: "You have {count} items, {1}"
+    }, {
+        count,
+        1: user.name
+    });"#
+    );
+    assert_eq!(
+        output.messages(),
+        &vec![json!({
+            "id": "WHt2US",
+            "defaultMessage": "You have {count} items, {1}"
+        })]
+    );
+}
+
+#[test]
+fn shadowed_tags() {
+    let src = r#"
+export function render(items) {
+  const t = (strings) => strings.join('')
+  return [t`Not a message`, items.map((t) => t`Neither`)]
+}
+
+export const greeting = t`Hello`
+"#;
+    let output = transform(src, options());
+
+    assert!(output.code.contains("t`Not a message`"));
+    assert!(output.code.contains("t`Neither`"));
+    assert_eq!(
+        output
+            .messages()
+            .iter()
+            .map(|message| &message["defaultMessage"])
+            .collect::<Vec<_>>(),
+        vec!["Hello"]
+    );
+}
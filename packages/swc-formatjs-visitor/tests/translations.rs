@@ -0,0 +1,89 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+static MESSAGES: &str = r#"
+import {FormattedMessage, useIntl} from 'react-intl'
+
+export function Greeting() {
+  const intl = useIntl()
+  return [
+    intl.formatMessage({id: 'greeting', defaultMessage: 'Hello'}),
+    <FormattedMessage id="farewell" defaultMessage="Goodbye" />,
+  ]
+}
+"#;
+
+fn options(catalog: serde_json::Value, ast: bool) -> serde_json::Value {
+    json!({
+        "ast": ast,
+        "translations": {
+            "locale": "fr-CA",
+            "catalogs": { "fr-CA": catalog, "fr": { "farewell": { "defaultMessage": "Au revoir" } } }
+        }
+    })
+}
+
+#[test]
+fn inline_translations() {
+    let output = transform(MESSAGES, options(json!({ "greeting": "Bonjour" }), false));
+
+    assert!(output.diagnostics.is_empty());
+    assert!(output.code.contains(r#"defaultMessage: "Bonjour""#));
+    assert!(output
+        .code
+        .contains(r#"<FormattedMessage id="farewell" defaultMessage="Au revoir"/>"#));
+    // The source messages are extracted.
+    assert_eq!(output.messages()[0]["defaultMessage"], "Hello");
+    assert_eq!(output.messages()[1]["defaultMessage"], "Goodbye");
+}
+
+#[test]
+fn invalid_translations() {
+    let output = transform(MESSAGES, options(json!({ "greeting": "Bonjour {" }), true));
+
+    assert_eq!(output.errors().len(), 1);
+    assert_eq!(
+        output.errors()[0].message,
+        "[React Intl] Translation of \"greeting\" for `fr-CA` failed to parse: SyntaxError: \
+         EXPECT_ARGUMENT_CLOSING_BRACE"
+    );
+    // The source message is inlined instead.
+    assert!(output.code.contains(r#"value: "Hello""#));
+    assert!(output.code.contains(r#"value: "Au revoir""#));
+}
+
+#[test]
+fn missing_translations() {
+    let output = transform(MESSAGES, options(json!({}), false));
+
+    assert_eq!(output.warnings().len(), 1);
+    assert_eq!(
+        output.warnings()[0].message,
+        "[React Intl] Missing translation of \"greeting\" for `fr-CA`."
+    );
+    assert!(output.code.contains(r#"defaultMessage: "Hello""#));
+
+    let mut options = options(json!({}), false);
+    options["translations"]["onMissing"] = json!("error");
+    let output = transform(MESSAGES, options);
+    assert_eq!(output.errors().len(), 1);
+    assert!(output.warnings().is_empty());
+}
+
+#[test]
+fn unreadable_catalog_files() {
+    let output = transform(
+        MESSAGES,
+        json!({
+            "translations": { "locale": "fr", "catalogFiles": { "fr": "missing/fr.json" } }
+        }),
+    );
+
+    assert_eq!(output.errors().len(), 1);
+    assert!(output.errors()[0]
+        .message
+        .starts_with("[React Intl] Cannot read translations of `fr` from `missing/fr.json`"));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::transform;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+static COMPONENT_VALUES: &str = r#"
+import {FormattedMessage} from 'react-intl'
+
+export const Greeting = ({name}) => (
+  <FormattedMessage
+    id="greeting"
+    defaultMessage="Hello <b>{name}</b>, you have {count} items"
+    values={{nmae: name, count: 1}}
+  />
+)
+"#;
+
+static CALL_VALUES: &str = r#"
+import {useIntl} from 'react-intl'
+
+export function Greeting({name}) {
+  const intl = useIntl()
+  return intl.formatMessage(
+    {id: 'greeting', defaultMessage: 'Hello <b>{name}</b>, see <link>the docs</link>'},
+    {name, b: 'bold'}
+  )
+}
+"#;
+
+fn messages(diagnostics: Vec<&common::Diagnostic>) -> Vec<(&str, Option<&str>)> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.message.as_str(), diagnostic.snippet.as_deref()))
+        .collect()
+}
+
+#[test]
+fn values_are_not_checked_by_default() {
+    assert!(transform(COMPONENT_VALUES, json!({}))
+        .diagnostics
+        .is_empty());
+    assert!(transform(CALL_VALUES, json!({})).diagnostics.is_empty());
+}
+
+#[test]
+fn component_values() {
+    let output = transform(COMPONENT_VALUES, json!({ "onError": { "values": "warn" } }));
+
+    assert!(output.errors().is_empty());
+    assert_eq!(
+        messages(output.warnings()),
+        vec![
+            (
+                "[React Intl] Missing value of `name`, which is used by the message.",
+                Some(
+                    r#"<FormattedMessage
+    id="greeting"
+    defaultMessage="Hello <b>{name}</b>, you have {count} items"
+    values={{nmae: name, count: 1}}
+  />"#
+                )
+            ),
+            (
+                "[React Intl] Value `nmae` is not used by the message.",
+                Some("nmae: name")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn call_values() {
+    let output = transform(CALL_VALUES, json!({ "onError": { "values": "error" } }));
+    assert_eq!(
+        messages(output.errors()),
+        vec![(
+            "[React Intl] Value of the rich text tag `b` must be a function.",
+            Some("b: 'bold'")
+        )]
+    );
+
+    // Missing tags may be provided by `defaultRichTextElements` of the `IntlProvider`.
+    let output = transform(
+        CALL_VALUES,
+        json!({ "onError": { "values": "error" }, "defaultRichTextElements": ["b"] }),
+    );
+    assert_eq!(
+        messages(output.errors()),
+        vec![
+            (
+                "[React Intl] Missing value of `link`, which is used by the message.",
+                Some("{name, b: 'bold'}")
+            ),
+            (
+                "[React Intl] Value of the rich text tag `b` must be a function.",
+                Some("b: 'bold'")
+            ),
+        ]
+    );
+}
@@ -1,6 +1,7 @@
 use swc_core::{
     ecma::{ast::Program, visit::*},
     plugin::{
+        errors::HANDLER,
         plugin_transform,
         proxies::TransformPluginProgramMetadata, metadata::TransformPluginMetadataContextKind,
     },
@@ -18,11 +19,22 @@ pub fn process(program: Program, metadata: TransformPluginProgramMetadata) -> Pr
 
     let plugin_config = metadata.get_transform_plugin_config();
     let plugin_options: FormatJSPluginOptions = if let Some(plugin_config) = plugin_config {
-        serde_json::from_str(&plugin_config).unwrap_or_else(|f| {
-            println!("Could not deserialize instrumentation option");
-            println!("{:#?}", f);
-            Default::default()
-        })
+        match serde_json::from_str(&plugin_config) {
+            Ok(plugin_options) => plugin_options,
+            Err(e) => {
+                // Fail the transform rather than silently falling back to the defaults,
+                // i.e. for a misspelled option.
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_err(&format!(
+                            "[React Intl] Invalid swc-plugin-formatjs options: {}",
+                            e
+                        ))
+                        .emit()
+                });
+                return program;
+            }
+        }
     } else {
         Default::default()
     };
@@ -4,7 +4,7 @@ import {injectIntl, FormattedMessage} from 'react-intl'
 const objectPointer = {
   id: 'foo.bar.invalid',
   defaultMessage: 'This cannot be extracted',
-  description: 'the plugin only supports inline objects',
+  description: 'const descriptors are inlined into the call',
 }
 
 class Foo extends Component {
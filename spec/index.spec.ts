@@ -44,6 +44,7 @@ test("additionalFunctionNames", function () {
   expect(
     transformAndCheck("additionalFunctionNames", {
       additionalFunctionNames: ["t"],
+      matchUnimportedNames: true,
     })
   ).toMatchInlineSnapshot(`
     {
@@ -74,14 +75,23 @@ test("additionalFunctionNames", function () {
           {
             "defaultMessage": "t",
             "id": "mfl9RV",
+            "meta": {
+              "project": "foo",
+            },
           },
           {
             "defaultMessage": "foo",
             "id": "9/u6bg",
+            "meta": {
+              "project": "foo",
+            },
           },
           {
             "defaultMessage": "foo2",
             "id": "3jMyCE",
+            "meta": {
+              "project": "foo",
+            },
           },
         ],
         "meta": {
@@ -103,16 +113,45 @@ test("ast", function () {
     import { FormattedMessage, defineMessage, defineMessages } from 'react-intl';
     defineMessage({
         id: 'defineMessage',
-        defaultMessage: '[{"type":0,"value":"this is a "},{"type":3,"value":"dt","style":"full"}]'
+        defaultMessage: [
+            {
+                type: 0,
+                value: "this is a "
+            },
+            {
+                type: 3,
+                value: "dt",
+                style: "full"
+            }
+        ]
     });
     defineMessages({
         foo: {
             id: 'defineMessages1',
-            defaultMessage: '[{"type":0,"value":"this is a "},{"type":4,"value":"dt","style":"full"}]'
+            defaultMessage: [
+                {
+                    type: 0,
+                    value: "this is a "
+                },
+                {
+                    type: 4,
+                    value: "dt",
+                    style: "full"
+                }
+            ]
         },
         bar: {
             id: 'defineMessages2',
-            defaultMessage: '[{"type":0,"value":"this is a "},{"type":2,"value":"dt"}]'
+            defaultMessage: [
+                {
+                    type: 0,
+                    value: "this is a "
+                },
+                {
+                    type: 2,
+                    value: "dt"
+                }
+            ]
         },
         baz: {
             id: 'compiled',
@@ -128,11 +167,45 @@ test("ast", function () {
         render() {
             Intl.formatMessage({
                 id: 'intl.formatMessage',
-                defaultMessage: '[{"type":0,"value":"foo "},{"type":6,"value":"s","options":{"one":{"value":[{"type":0,"value":"1"}]},"other":{"value":[{"type":0,"value":"2"}]}},"offset":0,"pluralType":"cardinal"}]'
+                defaultMessage: [
+                    {
+                        type: 0,
+                        value: "foo "
+                    },
+                    {
+                        type: 6,
+                        value: "s",
+                        options: {
+                            one: {
+                                value: [
+                                    {
+                                        type: 0,
+                                        value: "1"
+                                    }
+                                ]
+                            },
+                            other: {
+                                value: [
+                                    {
+                                        type: 0,
+                                        value: "2"
+                                    }
+                                ]
+                            }
+                        },
+                        offset: 0,
+                        pluralType: "cardinal"
+                    }
+                ]
             });
             return /*#__PURE__*/ React.createElement(React.Fragment, null, /*#__PURE__*/ React.createElement(FormattedMessage, {
                 id: "foo.bar.baz",
-                defaultMessage: "Hello World!"
+                defaultMessage: [
+                    {
+                        type: 0,
+                        value: "Hello World!"
+                    }
+                ]
             }), /*#__PURE__*/ React.createElement(FormattedMessage, {
                 id: "compiled2",
                 defaultMessage: [
@@ -140,7 +213,8 @@ test("ast", function () {
                         type: 0,
                         value: 'compiled comp'
                     }
-                ]
+                ],
+                description: "The default message."
             }));
         }
     }",
@@ -167,11 +241,6 @@ test("ast", function () {
             "description": "The default message.",
             "id": "foo.bar.baz",
           },
-          {
-            "defaultMessage": "",
-            "description": "The default message.",
-            "id": "compiled2",
-          },
         ],
         "meta": {},
       },
@@ -238,31 +307,49 @@ test("defineMessage", function () {
             "defaultMessage": "Hello World!",
             "description": "The default message",
             "id": "foo.bar.baz",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "Hello Nurse!",
             "description": "Another message",
             "id": "foo.bar.biff",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "{count, plural, =0 {😭} one {# kitten} other {# kittens}}",
             "description": "Counts kittens",
             "id": "app.home.kittens",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "Some whitespace",
             "description": "Whitespace",
             "id": "trailing.ws",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "A quoted value ''{value}'",
             "description": "Escaped apostrophe",
             "id": "escaped.apostrophe",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "This is message",
             "description": "Keys as a string literal",
             "id": "string.key.id",
+            "meta": {
+              "project": "amazing",
+            },
           },
         ],
         "meta": {
@@ -296,6 +383,9 @@ test("descriptionsAsObjects", function () {
               "text": "Something for the translator.",
             },
             "id": "foo.bar.baz",
+            "meta": {
+              "project": "amazing2",
+            },
           },
         ],
         "meta": {
@@ -359,26 +449,41 @@ test("defineMessages", function () {
             "defaultMessage": "Hello World!",
             "description": "The default message",
             "id": "foo.bar.baz",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "Hello Nurse!",
             "description": "Another message",
             "id": "foo.bar.biff",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "{count, plural, =0 {😭} one {# kitten} other {# kittens}}",
             "description": "Counts kittens",
             "id": "app.home.kittens",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "Some whitespace",
             "description": "Whitespace",
             "id": "trailing.ws",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "A quoted value ''{value}'",
             "description": "Escaped apostrophe",
             "id": "escaped.apostrophe",
+            "meta": {
+              "project": "amazing",
+            },
           },
         ],
         "meta": {
@@ -416,7 +521,7 @@ test("extractFromFormatMessageCall", function () {
     const objectPointer = {
         id: 'foo.bar.invalid',
         defaultMessage: 'This cannot be extracted',
-        description: 'the plugin only supports inline objects'
+        description: 'const descriptors are inlined into the call'
     };
     class Foo extends Component {
         render() {
@@ -435,7 +540,10 @@ test("extractFromFormatMessageCall", function () {
                     id: 'foo.bar.qux',
                     defaultMessage: "Hello Stranger!"
                 }),
-                invalid: this.props.intl.formatMessage(objectPointer)
+                invalid: this.props.intl.formatMessage({
+                    id: 'foo.bar.invalid',
+                    defaultMessage: "This cannot be extracted"
+                })
             };
             return /*#__PURE__*/ React.createElement("div", null, /*#__PURE__*/ React.createElement("h1", null, msgs.header), /*#__PURE__*/ React.createElement("p", null, msgs.content), /*#__PURE__*/ React.createElement("span", null, /*#__PURE__*/ React.createElement(FormattedMessage, {
                 id: "foo",
@@ -461,6 +569,11 @@ test("extractFromFormatMessageCall", function () {
             "description": "A different message",
             "id": "foo.bar.qux",
           },
+          {
+            "defaultMessage": "This cannot be extracted",
+            "description": "const descriptors are inlined into the call",
+            "id": "foo.bar.invalid",
+          },
           {
             "defaultMessage": "bar",
             "description": "baz",
@@ -543,7 +656,7 @@ test("formatMessageCall", function () {
     const objectPointer = {
         id: 'foo.bar.invalid',
         defaultMessage: 'This cannot be extracted',
-        description: 'the plugin only supports inline objects'
+        description: 'const descriptors are inlined into the call'
     };
     class Foo extends Component {
         render() {
@@ -556,7 +669,10 @@ test("formatMessageCall", function () {
                     id: 'foo.bar.biff',
                     defaultMessage: "Hello Nurse!"
                 }),
-                invalid: this.props.intl.formatMessage(objectPointer)
+                invalid: this.props.intl.formatMessage({
+                    id: 'foo.bar.invalid',
+                    defaultMessage: "This cannot be extracted"
+                })
             };
             return /*#__PURE__*/ React.createElement("div", null, /*#__PURE__*/ React.createElement("h1", null, msgs.header), /*#__PURE__*/ React.createElement("p", null, msgs.content), /*#__PURE__*/ React.createElement("span", null, /*#__PURE__*/ React.createElement(FormattedMessage, {
                 id: "foo",
@@ -577,6 +693,11 @@ test("formatMessageCall", function () {
             "description": "Another message",
             "id": "foo.bar.biff",
           },
+          {
+            "defaultMessage": "This cannot be extracted",
+            "description": "const descriptors are inlined into the call",
+            "id": "foo.bar.invalid",
+          },
           {
             "defaultMessage": "bar",
             "description": "baz",
@@ -1276,9 +1397,6 @@ test("preserveWhitespace", function () {
         },
         templateLinebreak: {
             id: 'templateLinebreak',
-            description: \`this is
-        a
-        description\`,
             defaultMessage: "this is\\n    a message"
         }
     });
@@ -1296,31 +1414,49 @@ test("preserveWhitespace", function () {
             "defaultMessage": "Hello World!",
             "description": "The default message",
             "id": "foo.bar.baz",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "Hello Nurse!",
             "description": "Another message",
             "id": "foo.bar.biff",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "{count, plural, =0 {😭} one {# kitten} other {# kittens}}",
             "description": "Counts kittens",
             "id": "app.home.kittens",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "   Some whitespace   ",
             "description": "Whitespace",
             "id": "trailing.ws",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "A quoted value ''{value}'",
             "description": "Escaped apostrophe",
             "id": "escaped.apostrophe",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "this is     a message",
             "description": "this is     a     description",
             "id": "newline",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "this is
@@ -1329,11 +1465,20 @@ test("preserveWhitespace", function () {
     a
     description",
             "id": "linebreak",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "this is
         a message",
+            "description": "this is
+        a
+        description",
             "id": "templateLinebreak",
+            "meta": {
+              "project": "amazing",
+            },
           },
           {
             "defaultMessage": "formatted message
@@ -1341,6 +1486,9 @@ test("preserveWhitespace", function () {
             "description": "foo
     						bar",
             "id": "inline.linebreak",
+            "meta": {
+              "project": "amazing",
+            },
           },
         ],
         "meta": {
@@ -64,6 +64,7 @@ export interface Options {
   removeDefaultMessage?: boolean
   additionalComponentNames?: string[]
   additionalFunctionNames?: string[]
+  matchUnimportedNames?: boolean
   pragma?: string
   extractSourceLocation?: boolean
   ast?: boolean